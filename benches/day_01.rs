@@ -0,0 +1,43 @@
+use advent_of_code_2023::{
+    day_01::{fast, generate, solve, vocabulary::Vocabulary},
+    rng::Rng,
+};
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+
+/// Checks one byte at a time, as the solver originally did.
+fn scalar(input: &str) -> Option<u32> {
+    input.lines().try_fold(0, |total, line| {
+        let mut digits = line.bytes().filter(u8::is_ascii_digit);
+        let first = digits.next()?;
+        let last = line.bytes().rfind(u8::is_ascii_digit)?;
+        Some(total + (first - b'0') as u32 * 10 + (last - b'0') as u32)
+    })
+}
+
+fn part_1(c: &mut Criterion) {
+    let mut group = c.benchmark_group("day_01_part_1");
+    for lines in [1_000, 100_000] {
+        let input = generate::generate(
+            &mut Rng::new(0),
+            &generate::Config {
+                lines,
+                ..Default::default()
+            },
+        );
+        group.throughput(Throughput::Bytes(input.len() as u64));
+        group.bench_with_input(BenchmarkId::new("scalar", lines), &input, |b, input| {
+            b.iter(|| scalar(input))
+        });
+        group.bench_with_input(BenchmarkId::new("automaton", lines), &input, |b, input| {
+            let vocabulary = Vocabulary::digits();
+            b.iter(|| solve(input, &vocabulary))
+        });
+        group.bench_with_input(BenchmarkId::new("swar", lines), &input, |b, input| {
+            b.iter(|| fast::part_1(input))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, part_1);
+criterion_main!(benches);
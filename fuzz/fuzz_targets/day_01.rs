@@ -0,0 +1,13 @@
+#![no_main]
+
+use advent_of_code_2023::day_01::*;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+    let matcher = vocabulary::Vocabulary::english().matcher();
+    for line in input.lines() {
+        first_and_last(line.as_bytes(), &matcher);
+    }
+    part_1(input);
+    part_2(input);
+});
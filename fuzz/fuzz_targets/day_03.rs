@@ -0,0 +1,9 @@
+#![no_main]
+
+use advent_of_code_2023::day_03::*;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+    part_1(input);
+    part_2(input);
+});
@@ -0,0 +1,9 @@
+#![no_main]
+
+use advent_of_code_2023::day_03::*;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: (u16, &[u8])| {
+    let (starting_index, line_bytes) = input;
+    scan_in_number(line_bytes, starting_index as usize);
+});
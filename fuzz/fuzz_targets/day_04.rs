@@ -0,0 +1,13 @@
+#![no_main]
+
+use advent_of_code_2023::day_04::*;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+    input.lines().for_each(|line| {
+        parse_card(line);
+    });
+    let input = input_generator(input);
+    part_1(&input);
+    part_2(&input);
+});
@@ -9,6 +9,7 @@ mod_name="day_$(printf '%02d' "$1")"
 
 mkdir -p "$SRC/$mod_name"
 sed "s|xx|$day|g" "$SRC/day_xx/mod.rs" > "$SRC/$mod_name/mod.rs"
+cp "$SRC/day_xx/reference.rs" "$SRC/$mod_name/reference.rs"
 sed -i -zE "s/(.*)(\naoc_lib.*)/\1pub mod $mod_name;\n\2/" "$SRC/lib.rs"
 sed -i -zE 's/(.*)(\n\s*println!\("done.*)/\1    run_day_with_generator!('"$mod_name, \"$day\");\n\2/" "$SRC/bin/bin.rs"
 cargo aoc input -d "$day" -y 2023
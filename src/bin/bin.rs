@@ -1,4 +1,7 @@
-use std::time::Instant;
+use std::{
+    sync::atomic::{AtomicUsize, Ordering},
+    time::Instant,
+};
 
 use advent_of_code_2023::*;
 
@@ -21,12 +24,27 @@ macro_rules! run_parts {
         let instant = Instant::now();
         let input = input_str!($d);
         let processed_input = $g(&input);
-        println!(
-            "day {0}-1: {1}\nday {0}-2: {2}",
-            $d,
-            $m::part_1(&processed_input),
-            $m::part_2(&processed_input)
-        );
+        if check_against_reference() {
+            check_part(
+                $d,
+                1,
+                $m::part_1(&processed_input),
+                $m::reference::part_1(&input),
+            );
+            check_part(
+                $d,
+                2,
+                $m::part_2(&processed_input),
+                $m::reference::part_2(&input),
+            );
+        } else {
+            println!(
+                "day {0}-1: {1}\nday {0}-2: {2}",
+                $d,
+                answer($m::part_1(&processed_input)),
+                answer($m::part_2(&processed_input))
+            );
+        }
 
         println!("{:?}\n", instant.elapsed());
     };
@@ -44,12 +62,139 @@ macro_rules! run_day {
     };
 }
 
+static MISMATCHES: AtomicUsize = AtomicUsize::new(0);
+
+/// Passing `--check` runs the reference solvers alongside the optimized ones.
+fn check_against_reference() -> bool {
+    std::env::args().any(|arg| arg == "--check")
+}
+
+fn answer(answer: Option<u32>) -> String {
+    answer.map_or_else(|| "invalid input".to_string(), |answer| answer.to_string())
+}
+
+fn check_part(day: &str, part: u32, optimized: Option<u32>, reference: u32) {
+    if optimized == Some(reference) {
+        println!("day {day}-{part}: {reference} (matches reference)");
+    } else {
+        MISMATCHES.fetch_add(1, Ordering::Relaxed);
+        println!(
+            "day {day}-{part}: MISMATCH optimized {}, reference {reference}",
+            answer(optimized)
+        );
+    }
+}
+
+/// `--bag "12 red, 13 green, 14 blue"` also answers day 2 part 1 for that bag.
+/// A bag that can't be read is reported and exits with an error.
+fn bag() -> Option<day_02::colours::ColourSet> {
+    let spec = std::env::args().skip_while(|arg| arg != "--bag").nth(1)?;
+    match spec.parse() {
+        Ok(bag) => Some(bag),
+        Err(error) => {
+            eprintln!("invalid bag: {error}");
+            std::process::exit(1);
+        }
+    }
+}
+
+fn run_day_02_with_bag(input: impl AsRef<str>, bag: &day_02::colours::ColourSet) {
+    let input = day_02::input_generator(input.as_ref());
+    let possible = day_02::bag::possible_games(&input, bag).count();
+    println!(
+        "day 2-1 with {bag}: {} ({possible} possible games)\n",
+        answer(day_02::bag::sum_possible(&input, bag))
+    );
+}
+
+/// A range written as `start..end`.
+fn parse_range(range: &str) -> Option<std::ops::Range<usize>> {
+    let (start, end) = range.split_once("..")?;
+    Some(start.parse().ok()?..end.parse().ok()?)
+}
+
+/// The range given for `what`, all of them if there isn't one, or exits
+/// with an error if it isn't a range.
+fn range_arg(range: Option<&String>, what: &str) -> std::ops::Range<usize> {
+    let Some(range) = range else {
+        return 0..usize::MAX;
+    };
+    parse_range(range).unwrap_or_else(|| {
+        eprintln!("invalid {what} {range:?}: expected a range like 2..10");
+        std::process::exit(1);
+    })
+}
+
+/// `render [rows] [cols]` draws the day 3 schematic, or the part of it in the
+/// given ranges.
+fn render_day_03(input: impl AsRef<str>, rows: Option<&String>, cols: Option<&String>) {
+    let viewport = day_03::render::Viewport {
+        rows: range_arg(rows, "rows"),
+        cols: range_arg(cols, "columns"),
+    };
+    let Some(schematic) = day_03::schematic::Schematic::parse(input.as_ref()) else {
+        eprintln!("{}", day_03::explain(input.as_ref()));
+        return;
+    };
+    print!(
+        "{}",
+        day_03::render::render(&schematic, &Default::default(), Some(&viewport))
+    );
+}
+
+/// `generate <day> [seed]` prints a random puzzle input instead of solving.
+fn generate(day: &str, seed: u64) -> Option<String> {
+    let mut rng = rng::Rng::new(seed);
+    Some(match day {
+        "1" => day_01::generate::generate(&mut rng, &Default::default()),
+        "2" => day_02::generate::generate(&mut rng, &Default::default()),
+        "3" => day_03::generate::generate(&mut rng, &Default::default()),
+        "4" => day_04::generate::generate(&mut rng, &Default::default()),
+        _ => return None,
+    })
+}
+
 pub fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if let [command, ranges @ ..] = &args[..] {
+        if command == "render" {
+            render_day_03(input_str!("3"), ranges.first(), ranges.get(1));
+            return;
+        }
+    }
+    if let [command, day, seed @ ..] = &args[..] {
+        if command == "generate" {
+            let seed = match seed.first().map_or(Ok(0), |seed| seed.parse()) {
+                Ok(seed) => seed,
+                Err(error) => {
+                    eprintln!("invalid seed {:?}: {error}", seed[0]);
+                    std::process::exit(1);
+                }
+            };
+            match generate(day, seed) {
+                Some(input) => print!("{input}"),
+                None => {
+                    eprintln!("no generator for day {day}");
+                    std::process::exit(1);
+                }
+            }
+            return;
+        }
+    }
+
+    let bag = bag();
     let instant = Instant::now();
     run_day!(day_01, "1");
     run_day_with_generator!(day_02, "2");
     run_day!(day_03, "3");
     run_day_with_generator!(day_04, "4");
 
+    if let Some(bag) = bag {
+        run_day_02_with_bag(input_str!("2"), &bag);
+    }
+
     println!("done in {:?}", instant.elapsed());
+    if MISMATCHES.load(Ordering::Relaxed) > 0 {
+        std::process::exit(1);
+    }
 }
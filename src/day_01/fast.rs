@@ -0,0 +1,109 @@
+//! Part 1 without the automaton, checking eight bytes at a time for digits.
+
+const LOW_BITS: u64 = 0x0101_0101_0101_0101;
+const HIGH_BITS: u64 = 0x8080_8080_8080_8080;
+
+/// The high bit of each byte of `word` that is an ASCII digit.
+///
+/// Every byte is checked exactly, unlike the usual "has zero byte" trick, so
+/// the mask can be searched from either end.
+fn digit_mask(word: u64) -> u64 {
+    let low = word & !HIGH_BITS;
+    // adding to a byte under 0x80 never carries into the next one
+    let at_least_zero = low + (0x80 - b'0' as u64) * LOW_BITS;
+    let past_nine = low + (0x80 - b':' as u64) * LOW_BITS;
+    at_least_zero & !past_nine & !word & HIGH_BITS
+}
+
+fn word(chunk: &[u8]) -> u64 {
+    u64::from_le_bytes(chunk.try_into().unwrap())
+}
+
+fn digit_value(byte: &u8) -> u32 {
+    (byte - b'0') as u32
+}
+
+fn first_digit(line: &[u8]) -> Option<u32> {
+    let mut chunks = line.chunks_exact(8);
+    for chunk in chunks.by_ref() {
+        let mask = digit_mask(word(chunk));
+        if mask != 0 {
+            return Some(digit_value(&chunk[mask.trailing_zeros() as usize / 8]));
+        }
+    }
+    chunks
+        .remainder()
+        .iter()
+        .find(|byte| byte.is_ascii_digit())
+        .map(digit_value)
+}
+
+fn last_digit(line: &[u8]) -> Option<u32> {
+    let mut chunks = line.rchunks_exact(8);
+    for chunk in chunks.by_ref() {
+        let mask = digit_mask(word(chunk));
+        if mask != 0 {
+            return Some(digit_value(&chunk[7 - mask.leading_zeros() as usize / 8]));
+        }
+    }
+    chunks
+        .remainder()
+        .iter()
+        .rfind(|byte| byte.is_ascii_digit())
+        .map(digit_value)
+}
+
+/// The same as solving with [`super::Vocabulary::digits`], finding the line
+/// breaks with `memchr`.
+pub fn part_1(input: &str) -> Option<u32> {
+    let bytes = input.as_bytes();
+    let mut start = 0;
+    let mut total = 0u32;
+    let line_ends = memchr::memchr_iter(b'\n', bytes).chain(
+        // the last line only counts if it isn't empty, as with `str::lines`
+        Some(bytes.len()).filter(|&end| end > 0 && bytes[end - 1] != b'\n'),
+    );
+    for end in line_ends {
+        let line = &bytes[start..end];
+        start = end + 1;
+        let value = first_digit(line)? * 10 + last_digit(line)?;
+        total = total.checked_add(value)?;
+    }
+    Some(total)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day_01::{solve, Vocabulary};
+    use proptest::prelude::*;
+
+    #[test]
+    fn digit_mask_is_exact() {
+        for byte in 0..=255u8 {
+            let expected = if byte.is_ascii_digit() { 0x80 } else { 0 };
+            assert_eq!(digit_mask(byte as u64), expected, "{byte:#x}");
+            assert_eq!(digit_mask(LOW_BITS * byte as u64), expected * LOW_BITS);
+        }
+    }
+
+    #[yare::parameterized(
+        short = { "a1b2c", Some((1, 2)) },
+        one_word = { "ab3cdef4", Some((3, 4)) },
+        across_words = { "abcdefgh1ijklmnopq2rs", Some((1, 2)) },
+        in_remainder = { "5abcdefghijklmnopq", Some((5, 5)) },
+        high_bytes = { "é9ü🎄8ÿ", Some((9, 8)) },
+        none = { "abcdefghijklmnop", None },
+    )]
+    fn first_and_last(line: &str, expected: Option<(u32, u32)>) {
+        let found = first_digit(line.as_bytes()).zip(last_digit(line.as_bytes()));
+        assert_eq!(found, expected);
+    }
+
+    proptest! {
+        #[test]
+        fn matches_automaton(input in "([a-z0-9é\\r]{0,20}\\n){0,10}[a-z0-9]{0,20}") {
+            prop_assert_eq!(part_1(&input), solve(&input, &Vocabulary::digits()));
+        }
+    }
+}
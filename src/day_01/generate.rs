@@ -0,0 +1,72 @@
+//! Random calibration documents for stress testing.
+
+use crate::rng::Rng;
+
+use super::DIGIT_WORDS;
+
+#[derive(Debug, Clone)]
+pub struct Config {
+    pub lines: usize,
+    /// The most digits and digit words on a single line.
+    pub max_tokens: u32,
+    /// The most filler letters between two tokens.
+    pub max_filler: u32,
+    /// How likely each token is to be spelled out rather than a digit.
+    pub word_probability: f64,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            lines: 1000,
+            max_tokens: 6,
+            max_filler: 5,
+            word_probability: 0.5,
+        }
+    }
+}
+
+fn filler(rng: &mut Rng, config: &Config, line: &mut String) {
+    for _ in 0..rng.between(0, config.max_filler) {
+        line.push(rng.between(b'a' as u32, b'z' as u32) as u8 as char);
+    }
+}
+
+/// A single line, which always contains at least one ASCII digit so that it
+/// is valid for both parts.
+pub fn line(rng: &mut Rng, config: &Config) -> String {
+    let tokens = rng.between(1, config.max_tokens.max(1));
+    let digit_token = rng.below(tokens as u64) as u32;
+    let mut line = String::new();
+    filler(rng, config, &mut line);
+    for token in 0..tokens {
+        let (word, value) = *rng.choose(&DIGIT_WORDS);
+        if token != digit_token && rng.chance(config.word_probability) {
+            line.push_str(word);
+        } else {
+            line.push(char::from_digit(value, 10).unwrap());
+        }
+        filler(rng, config, &mut line);
+    }
+    line
+}
+
+pub fn generate(rng: &mut Rng, config: &Config) -> String {
+    (0..config.lines)
+        .map(|_| line(rng, config) + "\n")
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_line_has_a_digit() {
+        let input = generate(&mut Rng::new(1), &Config::default());
+        assert_eq!(input.lines().count(), 1000);
+        assert!(input
+            .lines()
+            .all(|line| line.bytes().any(|b| b.is_ascii_digit())));
+    }
+}
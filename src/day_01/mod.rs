@@ -1,4 +1,18 @@
-const DIGIT_WORDS: [(&str, u32); 10] = [
+pub mod fast;
+pub mod generate;
+pub mod occurrences;
+pub mod reference;
+#[cfg(test)]
+mod strategies;
+pub mod vocabulary;
+
+use std::io::{self, BufRead};
+
+use crate::matcher::Matcher;
+use itertools::Itertools;
+use vocabulary::Vocabulary;
+
+pub const DIGIT_WORDS: [(&str, u32); 10] = [
     ("zero", 0),
     ("one", 1),
     ("two", 2),
@@ -11,60 +25,104 @@ const DIGIT_WORDS: [(&str, u32); 10] = [
     ("nine", 9),
 ];
 
-fn digit_or_word(
-    input_bytes: &[u8],
-    index_range: impl Iterator<Item = usize>,
-    word_list: &[(&str, u32)],
-) -> u32 {
-    for char_index in index_range {
-        let current_byte = input_bytes[char_index];
-        if current_byte.is_ascii_digit() {
-            return (current_byte - b'0') as u32;
-        }
-        for &(digit_word, value) in word_list {
-            if char_index + digit_word.len() > input_bytes.len() {
-                continue;
-            }
-            if &input_bytes[char_index..char_index + digit_word.len()] == digit_word.as_bytes() {
-                return value;
-            }
-        }
-    }
-    panic!("there are no digits")
+/// The values of the first and last digits in `line`, or `None` if it has none.
+pub fn first_and_last(line: &[u8], matcher: &Matcher<u32>) -> Option<(u32, u32)> {
+    Some((
+        matcher.find_first(line)?.value,
+        matcher.find_last(line)?.value,
+    ))
 }
 
-fn solve_part(input: &str, part_solver: impl Fn(&str) -> (u32, u32)) -> u32 {
-    input
-        .lines()
-        .map(part_solver)
-        .map(|(first, last)| first * 10 + last)
-        .sum()
+/// The digits of `first` followed by the digits of `last`.
+fn calibration_value(first: u32, last: u32) -> Option<u32> {
+    let shift = 10u32.checked_pow(last.checked_ilog10().unwrap_or(0) + 1)?;
+    first.checked_mul(shift)?.checked_add(last)
 }
 
-#[aoc(day1, part1)]
-pub fn part_1(input: &str) -> u32 {
-    solve_part(input, |s| {
-        (
-            digit_or_word(s.as_bytes(), 0..s.len(), &[]),
-            digit_or_word(s.as_bytes(), (0..s.len()).rev(), &[]),
-        )
+/// The sum of every line's calibration value, reading numbers with the given
+/// vocabulary.
+pub fn solve(input: &str, vocabulary: &Vocabulary) -> Option<u32> {
+    let matcher = vocabulary.matcher();
+    input.lines().try_fold(0u32, |total, line| {
+        let (first, last) = first_and_last(line.as_bytes(), &matcher)?;
+        total.checked_add(calibration_value(first, last)?)
     })
 }
 
+/// Like [`solve`], but reads one line at a time so memory use is bounded by
+/// the longest line rather than the whole document.
+pub fn solve_reader(mut reader: impl BufRead, vocabulary: &Vocabulary) -> io::Result<u64> {
+    let matcher = vocabulary.matcher();
+    let mut line = Vec::new();
+    let mut total = 0u64;
+    for line_number in 1.. {
+        line.clear();
+        if reader.read_until(b'\n', &mut line)? == 0 {
+            break;
+        }
+        let trimmed = line.strip_suffix(b"\n").unwrap_or(&line);
+        let trimmed = trimmed.strip_suffix(b"\r").unwrap_or(trimmed);
+        let value = first_and_last(trimmed, &matcher)
+            .and_then(|(first, last)| calibration_value(first, last))
+            .ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("line {line_number} has no calibration value"),
+                )
+            })?;
+        total = total.checked_add(u64::from(value)).ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("the total is too large at line {line_number}"),
+            )
+        })?;
+    }
+    Ok(total)
+}
+
+#[aoc(day1, part1)]
+pub fn part_1(input: &str) -> Option<u32> {
+    fast::part_1(input)
+}
+
 #[aoc(day1, part2)]
-pub fn part_2(input: &str) -> u32 {
-    solve_part(input, |s| {
-        (
-            digit_or_word(s.as_bytes(), 0..s.len(), &DIGIT_WORDS),
-            digit_or_word(s.as_bytes(), (0..s.len()).rev(), &DIGIT_WORDS),
-        )
-    })
+pub fn part_2(input: &str) -> Option<u32> {
+    solve(input, &Vocabulary::english())
+}
+
+/// The calibration value each part reads from every line, along with every
+/// number part 2 found.
+pub fn explain(input: &str) -> String {
+    let matchers = [
+        Vocabulary::digits().matcher(),
+        Vocabulary::english().matcher(),
+    ];
+    input
+        .lines()
+        .map(|line| {
+            let [part_1, part_2] = matchers.each_ref().map(|matcher| {
+                match occurrences::Scoring::FirstAndLast
+                    .score(&occurrences::occurrences(line, matcher))
+                {
+                    Some(value) => value.to_string(),
+                    None => "none".to_string(),
+                }
+            });
+            let tokens = occurrences::occurrences(line, &matchers[1])
+                .iter()
+                .map(|occurrence| format!("{}@{}", occurrence.token, occurrence.span.start))
+                .join(" ");
+            format!("{line}: part 1 {part_1}, part 2 {part_2} ({tokens})\n")
+        })
+        .collect()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use indoc::indoc;
+    use proptest::prelude::*;
+    use std::io::BufReader;
 
     #[test]
     fn test() {
@@ -76,7 +134,9 @@ mod tests {
             treb7uchet
             "
         };
-        assert_eq!(part_1(input1), 142);
+        assert_eq!(part_1(input1), Some(142));
+        assert_eq!(reference::part_1(input1), 142);
+        insta::assert_snapshot!("explain_part_1", explain(input1));
         let input2 = indoc! {
             "
             two1nine
@@ -88,7 +148,9 @@ mod tests {
             7pqrstsixteen
             "
         };
-        assert_eq!(part_2(input2), 281);
+        assert_eq!(part_2(input2), Some(281));
+        assert_eq!(reference::part_2(input2), 281);
+        insta::assert_snapshot!("explain_part_2", explain(input2));
     }
 
     #[yare::parameterized(
@@ -100,11 +162,8 @@ mod tests {
     fn part1_lines_test(input: &str, first_digit: u32, last_digit: u32) {
         dbg!(input);
         assert_eq!(
-            (
-                digit_or_word(input.as_bytes(), 0..input.len(), &[]),
-                digit_or_word(input.as_bytes(), (0..input.len()).rev(), &[]),
-            ),
-            (first_digit, last_digit)
+            first_and_last(input.as_bytes(), &Vocabulary::digits().matcher()),
+            Some((first_digit, last_digit))
         );
     }
 
@@ -120,11 +179,132 @@ mod tests {
     fn part2_lines_test(input: &str, first_digit: u32, last_digit: u32) {
         dbg!(input);
         assert_eq!(
-            (
-                digit_or_word(input.as_bytes(), 0..input.len(), &DIGIT_WORDS),
-                digit_or_word(input.as_bytes(), (0..input.len()).rev(), &DIGIT_WORDS),
-            ),
-            (first_digit, last_digit)
+            first_and_last(input.as_bytes(), &Vocabulary::english().matcher()),
+            Some((first_digit, last_digit))
+        );
+    }
+
+    #[yare::parameterized(
+        no_digits = { "abc", None },
+        blank_line = { "1\n\n2", None },
+        only_words = { "one", Some(11) },
+    )]
+    fn missing_digits(input: &str, expected_part_2: Option<u32>) {
+        assert_eq!(part_1(input), None);
+        assert_eq!(part_2(input), expected_part_2);
+    }
+
+    #[yare::parameterized(
+        accents = { "é1ü2ß", 12, 12 },
+        words_after_accents = { "ñone2nine", 22, 19 },
+        emoji = { "🎄7🎄", 77, 77 },
+    )]
+    fn non_ascii(input: &str, expected_part_1: u32, expected_part_2: u32) {
+        assert_eq!(reference::part_1(input), expected_part_1);
+        assert_eq!(reference::part_2(input), expected_part_2);
+        assert_eq!(part_1(input), Some(expected_part_1));
+        assert_eq!(part_2(input), Some(expected_part_2));
+    }
+
+    #[yare::parameterized(
+        lf = { "two1nine\neightwothree\n", 1, Some(112) },
+        crlf = { "two1nine\r\neightwothree\r\n", 1, Some(112) },
+        no_trailing_newline = { "two1nine\neightwothree", 1, Some(112) },
+        large_buffer = { "two1nine\neightwothree\n", 8192, Some(112) },
+        empty = { "", 1, Some(0) },
+        blank_line = { "two1nine\n\neightwothree\n", 1, None },
+    )]
+    fn reader(input: &str, capacity: usize, expected: Option<u64>) {
+        let reader = BufReader::with_capacity(capacity, input.as_bytes());
+        assert_eq!(solve_reader(reader, &Vocabulary::english()).ok(), expected);
+    }
+
+    /// Every pair of digit words where the end of the first can start the
+    /// second, like `eightwo`, along with their values.
+    fn overlapping_pairs() -> Vec<(String, u32, u32)> {
+        DIGIT_WORDS
+            .iter()
+            .cartesian_product(DIGIT_WORDS)
+            .flat_map(|(&(a, first), (b, last))| {
+                (1..a.len().min(b.len()))
+                    .filter(move |&overlap| a.ends_with(&b[..overlap]))
+                    .map(move |overlap| (format!("{a}{}", &b[overlap..]), first, last))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn overlapping_words() {
+        let pairs = overlapping_pairs();
+        assert_eq!(
+            pairs.iter().map(|(merged, ..)| merged).collect_vec(),
+            [
+                "zerone",
+                "oneight",
+                "twone",
+                "threeight",
+                "fiveight",
+                "sevenine",
+                "eightwo",
+                "eighthree",
+                "nineight"
+            ]
         );
+        let matcher = Vocabulary::english().matcher();
+        for (merged, first, last) in pairs {
+            for line in [
+                merged.clone(),
+                format!("x{merged}y"),
+                format!("{merged}{merged}"),
+            ] {
+                assert_eq!(
+                    first_and_last(line.as_bytes(), &matcher),
+                    Some((first, last)),
+                    "{line}"
+                );
+            }
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn matches_reference(input in strategies::document(1..50)) {
+            prop_assert_eq!(part_1(&input), Some(reference::part_1(&input)));
+            prop_assert_eq!(part_1(&input), solve(&input, &Vocabulary::digits()));
+            prop_assert_eq!(part_2(&input), Some(reference::part_2(&input)));
+            prop_assert_eq!(
+                solve_reader(input.as_bytes(), &Vocabulary::english()).ok(),
+                part_2(&input).map(u64::from)
+            );
+        }
+
+        #[test]
+        fn digits_ignore_padding(
+            line in strategies::line(),
+            prefix in "[^0-9\n]{0,8}",
+            suffix in "[^0-9\n]{0,8}",
+        ) {
+            let padded = format!("{prefix}{line}{suffix}");
+            let matcher = Vocabulary::digits().matcher();
+            prop_assert_eq!(
+                first_and_last(padded.as_bytes(), &matcher),
+                first_and_last(line.as_bytes(), &matcher)
+            );
+        }
+
+        // padding only uses characters that can't complete a digit word
+        #[test]
+        fn words_ignore_padding(
+            line in strategies::line(),
+            prefix in "[abcdjklmpqy.#]{0,8}",
+            suffix in "[abcdjklmpqy.#]{0,8}",
+        ) {
+            let padded = format!("{prefix}{line}{suffix}");
+            let matcher = Vocabulary::english().matcher();
+            prop_assert_eq!(
+                first_and_last(padded.as_bytes(), &matcher),
+                first_and_last(line.as_bytes(), &matcher)
+            );
+        }
     }
 }
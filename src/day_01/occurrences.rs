@@ -0,0 +1,194 @@
+//! Every number written in a calibration line, rather than just the first and
+//! last.
+
+use std::ops::Range;
+
+use crate::matcher::Matcher;
+
+use super::calibration_value;
+
+/// A token from the vocabulary found in a line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Occurrence<'a> {
+    /// The byte range of the token in the line.
+    pub span: Range<usize>,
+    /// The token as it's written in the line.
+    pub token: &'a str,
+    pub value: u32,
+}
+
+/// Every occurrence in `line`, overlapping ones included, ordered by where
+/// they start.
+pub fn occurrences<'a>(line: &'a str, matcher: &Matcher<u32>) -> Vec<Occurrence<'a>> {
+    let mut found: Vec<_> = matcher.find_iter(line.as_bytes()).collect();
+    found.sort_by_key(|found| (found.start, found.pattern));
+    found
+        .into_iter()
+        .filter_map(|found| {
+            // a token is valid UTF-8 and only its ASCII letters are folded, so
+            // it can only match on char boundaries, but a match that doesn't
+            // is dropped rather than sliced
+            Some(Occurrence {
+                span: found.start..found.end,
+                token: line.get(found.start..found.end)?,
+                value: found.value,
+            })
+        })
+        .collect()
+}
+
+/// Ways of turning a line's occurrences into a single number.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Scoring {
+    /// The puzzle's calibration value, the first value followed by the last.
+    FirstAndLast,
+    /// Every value added together.
+    Sum,
+    /// The first, middle and last values added together, where the middle of
+    /// an even number of occurrences is the later of the two.
+    FirstMiddleLast,
+}
+
+impl Scoring {
+    /// `None` if there are no occurrences or the score doesn't fit in a `u32`.
+    pub fn score(self, occurrences: &[Occurrence]) -> Option<u32> {
+        let first = occurrences.first()?.value;
+        let last = occurrences.last()?.value;
+        match self {
+            Scoring::FirstAndLast => calibration_value(first, last),
+            Scoring::Sum => occurrences.iter().try_fold(0u32, |total, occurrence| {
+                total.checked_add(occurrence.value)
+            }),
+            Scoring::FirstMiddleLast => first
+                .checked_add(occurrences[occurrences.len() / 2].value)?
+                .checked_add(last),
+        }
+    }
+}
+
+/// `line` with every occurrence in bold green, for printing to a terminal.
+/// The spans are byte ranges, so any that split a char are shown with
+/// replacement characters rather than panicking.
+pub fn highlight(line: &str, occurrences: &[Occurrence]) -> String {
+    let line = line.as_bytes();
+    let mut highlighted = Vec::new();
+    let mut written = 0;
+    for occurrence in occurrences {
+        // overlapping occurrences share one highlighted run
+        if occurrence.span.end <= written {
+            continue;
+        }
+        let start = occurrence.span.start.max(written);
+        if start > written || written == 0 {
+            highlighted.extend_from_slice(&line[written..start]);
+            highlighted.extend_from_slice(b"\x1b[1;32m");
+        } else {
+            // extending the previous run, so drop its reset
+            highlighted.truncate(highlighted.len() - b"\x1b[0m".len());
+        }
+        highlighted.extend_from_slice(&line[start..occurrence.span.end]);
+        highlighted.extend_from_slice(b"\x1b[0m");
+        written = occurrence.span.end;
+    }
+    highlighted.extend_from_slice(&line[written..]);
+    String::from_utf8_lossy(&highlighted).into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day_01::vocabulary::Vocabulary;
+
+    #[test]
+    fn overlapping_occurrences() {
+        let matcher = Vocabulary::english().matcher();
+        let found = occurrences("xtwone3", &matcher);
+        assert_eq!(
+            found,
+            [
+                Occurrence {
+                    span: 1..4,
+                    token: "two",
+                    value: 2
+                },
+                Occurrence {
+                    span: 3..6,
+                    token: "one",
+                    value: 1
+                },
+                Occurrence {
+                    span: 6..7,
+                    token: "3",
+                    value: 3
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn non_ascii() {
+        let matcher = Vocabulary::english()
+            .with("drei", 3)
+            .unwrap()
+            .with("ß", 8)
+            .unwrap()
+            .case_insensitive(true)
+            .matcher();
+        let found = occurrences("éONEßDREI", &matcher);
+        assert_eq!(
+            found,
+            [
+                Occurrence {
+                    span: 2..5,
+                    token: "ONE",
+                    value: 1
+                },
+                Occurrence {
+                    span: 5..7,
+                    token: "ß",
+                    value: 8
+                },
+                Occurrence {
+                    span: 7..11,
+                    token: "DREI",
+                    value: 3
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn highlight_split_char() {
+        let found = [Occurrence {
+            span: 1..2,
+            token: "é",
+            value: 1,
+        }];
+        assert_eq!(highlight("é", &found), "\u{fffd}\x1b[1;32m\u{fffd}\x1b[0m");
+    }
+
+    #[yare::parameterized(
+        calibration = { "two1nine", Scoring::FirstAndLast, Some(29) },
+        sum = { "two1nine", Scoring::Sum, Some(12) },
+        first_middle_last_odd = { "1two3four5", Scoring::FirstMiddleLast, Some(1 + 3 + 5) },
+        first_middle_last_even = { "1two3four", Scoring::FirstMiddleLast, Some(1 + 3 + 4) },
+        single = { "seven", Scoring::FirstMiddleLast, Some(21) },
+        none = { "abc", Scoring::Sum, None },
+    )]
+    fn scoring(line: &str, scoring: Scoring, expected: Option<u32>) {
+        let found = occurrences(line, &Vocabulary::english().matcher());
+        assert_eq!(scoring.score(&found), expected);
+    }
+
+    #[yare::parameterized(
+        none = { "abc", "abc" },
+        separate = { "a1b2", "a\x1b[1;32m1\x1b[0mb\x1b[1;32m2\x1b[0m" },
+        adjacent = { "12x", "\x1b[1;32m12\x1b[0mx" },
+        overlapping = { "xeightwo", "x\x1b[1;32meightwo\x1b[0m" },
+        non_ascii = { "é1ñone", "é\x1b[1;32m1\x1b[0mñ\x1b[1;32mone\x1b[0m" },
+    )]
+    fn highlighting(line: &str, expected: &str) {
+        let found = occurrences(line, &Vocabulary::english().matcher());
+        assert_eq!(highlight(line, &found), expected);
+    }
+}
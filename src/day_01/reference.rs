@@ -0,0 +1,42 @@
+//! Slow but straightforward solvers used to cross-check the optimized ones.
+
+const DIGIT_WORDS: [&str; 10] = [
+    "zero", "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+];
+
+/// Every digit found in `line`, in order of starting position.
+fn digits(line: &str, with_words: bool) -> Vec<u32> {
+    line.char_indices()
+        .filter_map(|(index, first)| {
+            let rest = &line[index..];
+            if let Some(digit) = first.to_digit(10) {
+                return Some(digit);
+            }
+            if !with_words {
+                return None;
+            }
+            (0..)
+                .zip(DIGIT_WORDS)
+                .find(|(_, word)| rest.starts_with(word))
+                .map(|(value, _)| value)
+        })
+        .collect()
+}
+
+fn solve(input: &str, with_words: bool) -> u32 {
+    input
+        .lines()
+        .map(|line| {
+            let digits = digits(line, with_words);
+            digits.first().unwrap_or(&0) * 10 + digits.last().unwrap_or(&0)
+        })
+        .sum()
+}
+
+pub fn part_1(input: &str) -> u32 {
+    solve(input, false)
+}
+
+pub fn part_2(input: &str) -> u32 {
+    solve(input, true)
+}
@@ -0,0 +1,26 @@
+//! Proptest strategies that build calibration documents token by token, so a
+//! failing case shrinks to the fewest and shortest lines that still fail.
+
+use proptest::{collection::vec, prelude::*};
+
+use super::DIGIT_WORDS;
+
+/// A digit, a digit word or a few filler letters.
+fn token() -> impl Strategy<Value = String> {
+    prop_oneof![
+        (0..10u32).prop_map(|digit| digit.to_string()),
+        prop::sample::select(DIGIT_WORDS.to_vec()).prop_map(|(word, _)| word.to_string()),
+        "[a-z]{1,5}",
+    ]
+}
+
+/// A line with at least one ASCII digit, so that it is valid for both parts.
+pub fn line() -> impl Strategy<Value = String> {
+    (vec(token(), 0..4), 0..10u32, vec(token(), 0..4))
+        .prop_map(|(before, digit, after)| format!("{}{digit}{}", before.concat(), after.concat()))
+}
+
+/// A document of `lines` lines, each ending in a newline.
+pub fn document(lines: std::ops::Range<usize>) -> impl Strategy<Value = String> {
+    vec(line(), lines).prop_map(|lines| lines.into_iter().map(|line| line + "\n").collect())
+}
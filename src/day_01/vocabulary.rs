@@ -0,0 +1,202 @@
+//! The tokens that can spell out a number in a calibration document.
+
+use std::{fmt, fs, io, path::Path, str::FromStr};
+
+use crate::matcher::Matcher;
+
+use super::DIGIT_WORDS;
+
+/// A list of tokens and the values they stand for.
+///
+/// Values may have more than one digit, in which case a line's calibration
+/// value is the first and last values written one after the other, so
+/// `twelve...3` reads as `123`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Vocabulary {
+    tokens: Vec<(String, u32)>,
+    case_insensitive: bool,
+}
+
+impl Vocabulary {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The digits `0` to `9`.
+    pub fn digits() -> Self {
+        Vocabulary {
+            tokens: (0..10).map(|digit| (digit.to_string(), digit)).collect(),
+            case_insensitive: false,
+        }
+    }
+
+    /// The digits along with the English words `zero` to `nine`.
+    pub fn english() -> Self {
+        let mut vocabulary = Self::digits();
+        vocabulary.tokens.extend(
+            DIGIT_WORDS
+                .iter()
+                .map(|&(word, value)| (word.to_string(), value)),
+        );
+        vocabulary
+    }
+
+    /// Adds a token, which is preferred over any later token starting at the
+    /// same place, or fails if it's empty since it would match everywhere.
+    pub fn with(mut self, token: impl Into<String>, value: u32) -> Result<Self, EmptyTokenError> {
+        let token = token.into();
+        if token.is_empty() {
+            return Err(EmptyTokenError);
+        }
+        self.tokens.push((token, value));
+        Ok(self)
+    }
+
+    /// Whether ASCII letters should match regardless of their case.
+    pub fn case_insensitive(mut self, case_insensitive: bool) -> Self {
+        self.case_insensitive = case_insensitive;
+        self
+    }
+
+    pub fn tokens(&self) -> &[(String, u32)] {
+        &self.tokens
+    }
+
+    /// Reads a vocabulary in the format accepted by [`Vocabulary::from_str`].
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        fs::read_to_string(path)?
+            .parse()
+            .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))
+    }
+
+    pub fn matcher(&self) -> Matcher<u32> {
+        let tokens = self.tokens.iter().map(|(token, value)| (token, *value));
+        if self.case_insensitive {
+            Matcher::new_ascii_case_insensitive(tokens)
+        } else {
+            Matcher::new(tokens)
+        }
+    }
+}
+
+/// An empty token passed to [`Vocabulary::with`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EmptyTokenError;
+
+impl fmt::Display for EmptyTokenError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "a vocabulary token can't be empty")
+    }
+}
+
+impl std::error::Error for EmptyTokenError {}
+
+/// A line of a vocabulary file that isn't a token followed by its value.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseVocabularyError {
+    pub line: usize,
+}
+
+impl fmt::Display for ParseVocabularyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {} should be a token and its value", self.line)
+    }
+}
+
+impl std::error::Error for ParseVocabularyError {}
+
+impl FromStr for Vocabulary {
+    type Err = ParseVocabularyError;
+
+    /// One `token value` pair per line. Blank lines and lines starting with
+    /// `#` are skipped.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.lines()
+            .enumerate()
+            .map(|(index, line)| (index + 1, line.trim()))
+            .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'))
+            .try_fold(Self::new(), |vocabulary, (line_number, line)| {
+                let error = || ParseVocabularyError { line: line_number };
+                match line.split_whitespace().collect::<Vec<_>>()[..] {
+                    [token, value] => vocabulary
+                        .with(token, value.parse().map_err(|_| error())?)
+                        .map_err(|_| error()),
+                    _ => Err(error()),
+                }
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day_01::solve;
+    use indoc::indoc;
+
+    fn with(vocabulary: Vocabulary, tokens: &[(&str, u32)]) -> Vocabulary {
+        tokens
+            .iter()
+            .fold(vocabulary, |vocabulary, &(token, value)| {
+                vocabulary.with(token, value).unwrap()
+            })
+    }
+
+    #[test]
+    fn parse() {
+        let vocabulary: Vocabulary = indoc! {
+            "
+            # french
+            un 1
+
+            deux   2
+            "
+        }
+        .parse()
+        .unwrap();
+        assert_eq!(
+            vocabulary,
+            with(Vocabulary::new(), &[("un", 1), ("deux", 2)])
+        );
+    }
+
+    #[yare::parameterized(
+        missing_value = { "un 1\ndeux", 2 },
+        bad_value = { "un one", 1 },
+        extra_field = { "# comment\nun 1 2", 2 },
+    )]
+    fn parse_error(input: &str, line: usize) {
+        assert_eq!(
+            input.parse::<Vocabulary>(),
+            Err(ParseVocabularyError { line })
+        );
+    }
+
+    #[yare::parameterized(
+        french = { "un2\ndeuxtrois", with(Vocabulary::digits(), &[("un", 1), ("deux", 2), ("trois", 3)]), Some(12 + 23) },
+        roman = { "xiv", with(Vocabulary::new(), &[("i", 1), ("v", 5), ("x", 10)]), Some(105) },
+        multi_digit = { "twelve3ten", with(Vocabulary::english(), &[("ten", 10), ("twelve", 12)]), Some(1210) },
+        ordinals = { "first-third", with(Vocabulary::new(), &[("first", 1), ("third", 3)]), Some(13) },
+        no_tokens = { "one", Vocabulary::digits(), None },
+    )]
+    fn custom_vocabulary(input: &str, vocabulary: Vocabulary, expected: Option<u32>) {
+        assert_eq!(solve(input, &vocabulary), expected);
+    }
+
+    #[test]
+    fn empty_token() {
+        assert_eq!(Vocabulary::digits().with("", 1), Err(EmptyTokenError));
+        assert_eq!(
+            EmptyTokenError.to_string(),
+            "a vocabulary token can't be empty"
+        );
+    }
+
+    #[yare::parameterized(
+        sensitive = { false, Some(11) },
+        insensitive = { true, Some(21) },
+    )]
+    fn case_insensitive(case_insensitive: bool, expected: Option<u32>) {
+        let vocabulary = Vocabulary::english().case_insensitive(case_insensitive);
+        assert_eq!(solve("TWO1", &vocabulary), expected);
+    }
+}
@@ -0,0 +1,208 @@
+//! Questions about which bags the recorded games could have been played with.
+
+use itertools::Itertools;
+
+use super::{colours::ColourSet, Game, Input};
+
+/// The bag part 1 asks about: 12 red, 13 green and 14 blue cubes.
+pub fn puzzle() -> ColourSet {
+    ColourSet::new()
+        .with("red", 12)
+        .with("green", 13)
+        .with("blue", 14)
+}
+
+/// The games that never showed more cubes of a colour than `bag` holds.
+pub fn possible_games<'a>(input: &'a Input, bag: &'a ColourSet) -> impl Iterator<Item = &'a Game> {
+    input
+        .games
+        .iter()
+        .filter(|game| game.max_colours.fits_within(bag))
+}
+
+/// The sum of the ids of the games that are possible with `bag`.
+pub fn sum_possible(input: &Input, bag: &ColourSet) -> Option<u32> {
+    possible_games(input, bag)
+        .map(|game| game.id)
+        .try_fold(0u32, u32::checked_add)
+}
+
+/// The bag with the fewest cubes that at least `games` of the games are
+/// possible with, or `None` if there aren't that many games.
+///
+/// Every count worth trying is none or one some game needed, and none below
+/// the `games`th smallest need of its colour, so with `n` games and `c`
+/// colours this tries at most `(n - games + 1)^(c - 1)` bags, each in
+/// `O(c n log n)`. That's still exponential in the number of colours, but
+/// counts that can't beat the best bag so far are cut off using a lower bound
+/// on the colours left, so the usual three colours are quick.
+pub fn smallest_bag(input: &Input, games: usize) -> Option<ColourSet> {
+    let palette = input.palette().into_iter().collect_vec();
+    let needs = input
+        .games
+        .iter()
+        .map(|game| &game.max_colours)
+        .collect_vec();
+    smallest_bag_for(&needs, &palette, games, u64::MAX)
+}
+
+fn size(bag: &ColourSet) -> u64 {
+    bag.iter().map(|(_, count)| u64::from(count)).sum()
+}
+
+/// The fewest cubes of `colour` that at least `games` of `needs` fit within.
+fn fewest(needs: &[&ColourSet], colour: &str, games: usize) -> Option<u32> {
+    match games.checked_sub(1) {
+        Some(index) => needs
+            .iter()
+            .map(|need| need.get(colour))
+            .sorted_unstable()
+            .nth(index),
+        None => Some(0),
+    }
+}
+
+/// The smallest bag of the colours in `palette` that at least `games` of
+/// `needs` fit within, if there's one with fewer than `limit` cubes.
+fn smallest_bag_for(
+    needs: &[&ColourSet],
+    palette: &[&str],
+    games: usize,
+    limit: u64,
+) -> Option<ColourSet> {
+    match palette {
+        [] => (needs.len() >= games && limit > 0).then(ColourSet::new),
+        // the cheapest way to fit enough games is the count the last of the
+        // smallest of them needs
+        [colour] => {
+            let count = fewest(needs, colour, games)?;
+            (u64::from(count) < limit).then(|| ColourSet::new().with(*colour, count))
+        }
+        [colour, rest @ ..] => {
+            let least = fewest(needs, colour, games)?;
+            let rest_least = rest
+                .iter()
+                .map(|colour| Some(u64::from(fewest(needs, colour, games)?)))
+                .sum::<Option<u64>>()?;
+            let counts = needs
+                .iter()
+                .map(|need| need.get(colour))
+                .chain([least])
+                .filter(|&count| count >= least)
+                .sorted_unstable()
+                .dedup();
+            let (mut best, mut limit) = (None, limit);
+            for count in counts {
+                // the rest never need fewer cubes than they would if every game
+                // fitted, and the counts only go up from here
+                if u64::from(count) + rest_least >= limit {
+                    break;
+                }
+                let fitting = needs
+                    .iter()
+                    .filter(|need| need.get(colour) <= count)
+                    .copied()
+                    .collect_vec();
+                let rest_limit = limit - u64::from(count);
+                if let Some(bag) = smallest_bag_for(&fitting, rest, games, rest_limit) {
+                    let bag = bag.with(*colour, count);
+                    limit = size(&bag);
+                    best = Some(bag);
+                }
+            }
+            best
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day_02::{input_generator, strategies, EXAMPLE};
+    use proptest::prelude::*;
+
+    #[yare::parameterized(
+        puzzle = { "12 red, 13 green, 14 blue", &[1, 2, 5] },
+        roomy = { "20 red, 13 green, 15 blue", &[1, 2, 3, 4, 5] },
+        no_green = { "20 red, 20 blue", &[] },
+        extra_colour = { "6 red, 3 green, 6 blue, 9 yellow", &[1, 2, 5] },
+    )]
+    fn possible(bag: &str, expected_ids: &[u32]) {
+        let input = input_generator(EXAMPLE);
+        let bag = bag.parse().unwrap();
+        let ids = possible_games(&input, &bag)
+            .map(|game| game.id)
+            .collect_vec();
+        assert_eq!(ids, expected_ids);
+    }
+
+    #[yare::parameterized(
+        none = { 0, Some("") },
+        one = { 1, Some("4 blue, 3 green, 1 red") },
+        three = { 3, Some("6 blue, 3 green, 6 red") },
+        all = { 5, Some("15 blue, 13 green, 20 red") },
+        too_many = { 6, None },
+    )]
+    fn smallest(games: usize, expected: Option<&str>) {
+        let input = input_generator(EXAMPLE);
+        let bag = smallest_bag(&input, games);
+        assert_eq!(bag, expected.map(|bag| bag.parse().unwrap()));
+        if let Some(bag) = bag {
+            assert!(possible_games(&input, &bag).count() >= games);
+        }
+    }
+
+    /// Without the lower bound, eight colours over forty games would be too
+    /// many bags to try.
+    #[test]
+    fn many_colours() {
+        let colours = [
+            "red", "green", "blue", "cyan", "magenta", "yellow", "black", "white",
+        ];
+        let input = (1..=40)
+            .map(|id: u32| {
+                let draw = colours
+                    .iter()
+                    .enumerate()
+                    .map(|(index, colour)| {
+                        format!("{} {colour}", (id * 7 + index as u32 * 13) % 17 + 1)
+                    })
+                    .join(", ");
+                format!("Game {id}: {draw}\n")
+            })
+            .collect::<String>();
+        let input = input_generator(&input);
+        for games in [1, 10, 20, 30, 40] {
+            let bag = smallest_bag(&input, games).unwrap();
+            assert!(possible_games(&input, &bag).count() >= games);
+        }
+    }
+
+    /// Tries every selection of games, taking the cubes each one needs.
+    fn smallest_size_by_subsets(input: &Input, games: usize) -> Option<u64> {
+        (games..=input.games.len())
+            .flat_map(|size| input.games.iter().combinations(size))
+            .map(|selected| {
+                selected
+                    .iter()
+                    .fold(ColourSet::new(), |bag, game| bag.max(&game.max_colours))
+            })
+            .map(|bag| size(&bag))
+            .min()
+    }
+
+    proptest! {
+        #[test]
+        fn smallest_matches_subsets(
+            input in strategies::document(1..8, 3, 6),
+            wanted in 0..9usize,
+        ) {
+            let input = input_generator(&input);
+            let bag = smallest_bag(&input, wanted);
+            prop_assert_eq!(bag.as_ref().map(size), smallest_size_by_subsets(&input, wanted));
+            if let Some(bag) = bag {
+                prop_assert!(possible_games(&input, &bag).count() >= wanted);
+            }
+        }
+    }
+}
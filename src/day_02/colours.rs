@@ -0,0 +1,230 @@
+//! Cube counts keyed by colour name, for any colours a game mentions.
+
+use std::{collections::BTreeMap, fmt, str::FromStr};
+
+/// A count of cubes for each colour. Colours that aren't listed have no
+/// cubes.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct ColourSet {
+    // zero counts are never stored, so equal sets compare equal
+    counts: BTreeMap<String, u32>,
+}
+
+impl ColourSet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the count for `colour`, replacing any previous count.
+    pub fn with(mut self, colour: impl Into<String>, count: u32) -> Self {
+        self.set(colour, count);
+        self
+    }
+
+    pub fn set(&mut self, colour: impl Into<String>, count: u32) {
+        let colour = colour.into();
+        match count {
+            0 => self.counts.remove(&colour),
+            _ => self.counts.insert(colour, count),
+        };
+    }
+
+    pub fn get(&self, colour: &str) -> u32 {
+        self.counts.get(colour).copied().unwrap_or(0)
+    }
+
+    /// The colours with at least one cube, in alphabetical order.
+    pub fn colours(&self) -> impl Iterator<Item = &str> {
+        self.counts.keys().map(String::as_str)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&str, u32)> {
+        self.counts
+            .iter()
+            .map(|(colour, &count)| (colour.as_str(), count))
+    }
+
+    pub fn total(&self) -> Option<u32> {
+        self.counts
+            .values()
+            .try_fold(0u32, |total, &count| total.checked_add(count))
+    }
+
+    /// The cubes in both sets together, or `None` if a count overflows.
+    pub fn checked_add(&self, other: &ColourSet) -> Option<ColourSet> {
+        other
+            .iter()
+            .try_fold(self.clone(), |mut sum, (colour, count)| {
+                sum.set(colour, sum.get(colour).checked_add(count)?);
+                Some(sum)
+            })
+    }
+
+    /// The larger count of each colour.
+    pub fn max(&self, other: &ColourSet) -> ColourSet {
+        other.iter().fold(self.clone(), |mut max, (colour, count)| {
+            max.set(colour, max.get(colour).max(count));
+            max
+        })
+    }
+
+    /// Whether every colour here has no more cubes than in `bag`.
+    pub fn fits_within(&self, bag: &ColourSet) -> bool {
+        self.iter().all(|(colour, count)| count <= bag.get(colour))
+    }
+
+    /// The product of the counts of each colour in `palette`, which is zero
+    /// if any of them are missing.
+    pub fn power<'a>(&self, palette: impl IntoIterator<Item = &'a str>) -> Option<u32> {
+        palette
+            .into_iter()
+            .try_fold(1u32, |power, colour| power.checked_mul(self.get(colour)))
+    }
+}
+
+impl fmt::Display for ColourSet {
+    /// The counts the way a draw is written, such as `6 blue, 2 green`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (index, (colour, count)) in self.iter().enumerate() {
+            if index > 0 {
+                f.write_str(", ")?;
+            }
+            write!(f, "{count} {colour}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Part of a colour set that isn't a count followed by a colour.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseColourSetError {
+    pub part: String,
+}
+
+impl fmt::Display for ParseColourSetError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "`{}` should be a count and a colour", self.part)
+    }
+}
+
+impl std::error::Error for ParseColourSetError {}
+
+impl FromStr for ColourSet {
+    type Err = ParseColourSetError;
+
+    /// Comma separated `count colour` pairs, as in a draw. Colours are
+    /// lowercased and repeated colours are added together.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.split(',')
+            .map(str::trim)
+            .filter(|part| !part.is_empty())
+            .try_fold(Self::new(), |set, part| {
+                let error = || ParseColourSetError {
+                    part: part.to_string(),
+                };
+                match part.split_whitespace().collect::<Vec<_>>()[..] {
+                    [count, colour] => {
+                        let count = count.parse().map_err(|_| error())?;
+                        set.checked_add(&Self::new().with(colour.to_lowercase(), count))
+                            .ok_or_else(error)
+                    }
+                    _ => Err(error()),
+                }
+            })
+    }
+}
+
+impl<C: Into<String>> FromIterator<(C, u32)> for ColourSet {
+    fn from_iter<T: IntoIterator<Item = (C, u32)>>(iter: T) -> Self {
+        iter.into_iter()
+            .fold(Self::new(), |set, (colour, count)| set.with(colour, count))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn set(counts: &[(&str, u32)]) -> ColourSet {
+        counts.iter().copied().collect()
+    }
+
+    #[test]
+    fn zero_counts_are_missing() {
+        assert_eq!(set(&[("red", 0)]), ColourSet::new());
+        assert_eq!(set(&[("red", 1)]).with("red", 0), ColourSet::new());
+    }
+
+    #[yare::parameterized(
+        disjoint = { &[("red", 1)], &[("yellow", 2)], &[("red", 1), ("yellow", 2)] },
+        shared = { &[("red", 1), ("blue", 5)], &[("red", 3)], &[("red", 3), ("blue", 5)] },
+    )]
+    fn max(lhs: &[(&str, u32)], rhs: &[(&str, u32)], expected: &[(&str, u32)]) {
+        assert_eq!(set(lhs).max(&set(rhs)), set(expected));
+        assert_eq!(set(rhs).max(&set(lhs)), set(expected));
+    }
+
+    #[yare::parameterized(
+        disjoint = { &[("red", 1)], &[("yellow", 2)], Some(set(&[("red", 1), ("yellow", 2)])) },
+        shared = { &[("red", 1)], &[("red", 3)], Some(set(&[("red", 4)])) },
+        overflow = { &[("red", u32::MAX)], &[("red", 1)], None },
+    )]
+    fn checked_add(lhs: &[(&str, u32)], rhs: &[(&str, u32)], expected: Option<ColourSet>) {
+        assert_eq!(set(lhs).checked_add(&set(rhs)), expected);
+    }
+
+    #[yare::parameterized(
+        smaller = { &[("red", 1)], true },
+        equal = { &[("red", 2), ("purple", 1)], true },
+        larger = { &[("red", 3)], false },
+        unknown_colour = { &[("yellow", 1)], false },
+    )]
+    fn fits_within(draw: &[(&str, u32)], expected: bool) {
+        let bag = set(&[("red", 2), ("purple", 1)]);
+        assert_eq!(set(draw).fits_within(&bag), expected);
+    }
+
+    #[yare::parameterized(
+        draw = { "3 blue, 4 red", &[("red", 4), ("blue", 3)] },
+        spacing = { " 12 Red,13 green ,", &[("red", 12), ("green", 13)] },
+        repeated_colour = { "1 red, 2 red", &[("red", 3)] },
+        empty = { "", &[] },
+    )]
+    fn parse(input: &str, expected: &[(&str, u32)]) {
+        assert_eq!(input.parse(), Ok(set(expected)));
+    }
+
+    #[yare::parameterized(
+        missing_count = { "red", "red" },
+        bad_count = { "1 red, many blue", "many blue" },
+        extra_field = { "1 dark red", "1 dark red" },
+        overflow = { "4294967295 red, 1 red", "1 red" },
+    )]
+    fn parse_error(input: &str, part: &str) {
+        assert_eq!(
+            input.parse::<ColourSet>(),
+            Err(ParseColourSetError {
+                part: part.to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn display_round_trips() {
+        let bag = set(&[("red", 12), ("green", 13), ("blue", 14)]);
+        assert_eq!(bag.to_string(), "14 blue, 13 green, 12 red");
+        assert_eq!(bag.to_string().parse(), Ok(bag));
+    }
+
+    #[yare::parameterized(
+        all_present = { &["red", "yellow"], Some(6) },
+        missing = { &["red", "blue"], Some(0) },
+        empty_palette = { &[], Some(1) },
+    )]
+    fn power(palette: &[&str], expected: Option<u32>) {
+        assert_eq!(
+            set(&[("red", 2), ("yellow", 3)]).power(palette.iter().copied()),
+            expected
+        );
+    }
+}
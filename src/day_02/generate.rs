@@ -0,0 +1,61 @@
+//! Random game records for stress testing.
+
+use itertools::Itertools;
+
+use crate::rng::Rng;
+
+const COLOURS: [&str; 3] = ["red", "green", "blue"];
+
+#[derive(Debug, Clone)]
+pub struct Config {
+    pub games: usize,
+    /// The most draws recorded for a single game.
+    pub max_draws: u32,
+    /// The most cubes of one colour shown in a single draw.
+    pub max_cubes: u32,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            games: 100,
+            max_draws: 6,
+            max_cubes: 20,
+        }
+    }
+}
+
+/// A draw showing a random, non-empty selection of colours in random order.
+fn draw(rng: &mut Rng, config: &Config) -> String {
+    let mut colours = COLOURS;
+    rng.shuffle(&mut colours);
+    let shown = rng.between(1, COLOURS.len() as u32) as usize;
+    colours[..shown]
+        .iter()
+        .map(|colour| format!("{} {colour}", rng.between(1, config.max_cubes.max(1))))
+        .join(", ")
+}
+
+pub fn generate(rng: &mut Rng, config: &Config) -> String {
+    (1..=config.games)
+        .map(|id| {
+            let draws = (0..rng.between(1, config.max_draws.max(1)))
+                .map(|_| draw(rng, config))
+                .join("; ");
+            format!("Game {id}: {draws}\n")
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day_02::{input_generator, parse_game_line};
+
+    #[test]
+    fn every_game_parses() {
+        let input = generate(&mut Rng::new(1), &Config::default());
+        assert!(input.lines().all(|line| parse_game_line(line).is_some()));
+        assert_eq!(input_generator(&input).games.len(), 100);
+    }
+}
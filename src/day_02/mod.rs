@@ -1,36 +1,106 @@
+pub mod bag;
+pub mod colours;
+pub mod generate;
+pub mod reference;
+pub mod simulation;
+#[cfg(test)]
+mod strategies;
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use colours::ColourSet;
 use itertools::Itertools;
 use nom::{
-    branch::alt,
     bytes::{
         complete::{tag, take_while1},
         streaming::tag_no_case,
     },
-    character::complete::space1,
+    character::complete::{alpha1, space1},
     combinator::{map_res, recognize},
+    error::{Error, ErrorKind},
     multi::separated_list1,
     sequence::{preceded, separated_pair, Tuple},
     IResult, Parser,
 };
 
-type Colours = (u32, u32, u32);
-fn add_colours(lhs: Colours, rhs: Colours) -> Colours {
-    (lhs.0 + rhs.0, lhs.1 + rhs.1, lhs.2 + rhs.2)
-}
-fn colours_scale(target: Colours, scale: u32) -> Colours {
-    (target.0 * scale, target.1 * scale, target.2 * scale)
-}
+/// The colours the puzzle says are in the bag.
+const PUZZLE_COLOURS: [&str; 3] = ["red", "green", "blue"];
+
+/// The example record from the puzzle.
+#[cfg(test)]
+pub(crate) const EXAMPLE: &str = indoc::indoc! {
+    "
+    Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
+    Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
+    Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
+    Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
+    Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green
+    "
+};
 
 #[derive(Debug)]
-struct Game {
+pub struct Game {
     id: u32,
-    // r, g, b
-    max_colours: Colours,
+    /// The cubes shown in each draw, in the order they were recorded.
+    draws: Vec<ColourSet>,
+    max_colours: ColourSet,
+}
+
+impl Game {
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    /// The most cubes of each colour shown in any one draw, which is also the
+    /// smallest bag the game could have been played with.
+    pub fn max_colours(&self) -> &ColourSet {
+        &self.max_colours
+    }
+
+    pub fn draws(&self) -> &[ColourSet] {
+        &self.draws
+    }
+
+    /// The indices of the draws that showed more cubes than `bag` holds.
+    pub fn impossible_draws<'a>(&'a self, bag: &'a ColourSet) -> impl Iterator<Item = usize> + 'a {
+        self.draws
+            .iter()
+            .positions(move |draw| !draw.fits_within(bag))
+    }
+
+    /// The first draw that rules out `bag`, or `None` if the game is possible
+    /// with it.
+    pub fn first_impossible_draw(&self, bag: &ColourSet) -> Option<usize> {
+        self.impossible_draws(bag).next()
+    }
+
+    /// The mean number of cubes of each colour shown per draw, counting draws
+    /// that didn't show a colour as none.
+    pub fn mean_shown(&self) -> BTreeMap<&str, f64> {
+        let mut totals = BTreeMap::new();
+        for (colour, count) in self.draws.iter().flat_map(ColourSet::iter) {
+            *totals.entry(colour).or_insert(0.0) += f64::from(count);
+        }
+        let draws = self.draws.len() as f64;
+        totals.values_mut().for_each(|total| *total /= draws);
+        totals
+    }
 }
 
 pub struct Input {
     games: Vec<Game>,
 }
 
+impl Input {
+    /// Every colour shown in any game.
+    pub fn palette(&self) -> BTreeSet<&str> {
+        self.games
+            .iter()
+            .flat_map(|game| game.max_colours.colours())
+            .collect()
+    }
+}
+
 fn game_id(input: &str) -> IResult<&str, u32> {
     preceded(
         tag_no_case("game "),
@@ -38,38 +108,36 @@ fn game_id(input: &str) -> IResult<&str, u32> {
     )(input)
 }
 
-fn game_view(input: &str) -> IResult<&str, Colours> {
-    let result: IResult<&str, Vec<Colours>> = separated_list1(
+fn game_view(input: &str) -> IResult<&str, ColourSet> {
+    let (rest, parts) = separated_list1(
         tag(", "),
         separated_pair(
             map_res(recognize(take_while1(char::is_numeric)), str::parse::<u32>),
             space1,
-            alt((
-                tag_no_case("red").map(|_| (1, 0, 0)),
-                tag_no_case("green").map(|_| (0, 1, 0)),
-                tag_no_case("blue").map(|_| (0, 0, 1)),
-            )),
-        )
-        .map(|(num, colours)| colours_scale(colours, num)),
-    )(input);
-    result.map(|(result, parts)| {
-        (
-            result,
-            parts.into_iter().reduce(add_colours).unwrap_or_default(),
+            alpha1.map(str::to_lowercase),
         )
-    })
+        .map(|(num, colour)| ColourSet::new().with(colour, num)),
+    )(input)?;
+    match parts
+        .iter()
+        .try_fold(ColourSet::new(), |view, part| view.checked_add(part))
+    {
+        Some(view) => Ok((rest, view)),
+        None => Err(nom::Err::Error(Error::new(input, ErrorKind::TooLarge))),
+    }
 }
 
-fn parse_game_line(input: &str) -> Option<Game> {
+pub fn parse_game_line(input: &str) -> Option<Game> {
     let (game_id, _, views) = (game_id, tag(": "), separated_list1(tag("; "), game_view))
         .parse(input)
         .ok()?
         .1;
     Some(Game {
         id: game_id,
-        max_colours: views.iter().fold((0, 0, 0), |(r0, r1, r2), &(v0, v1, v2)| {
-            (r0.max(v0), r1.max(v1), r2.max(v2))
-        }),
+        max_colours: views
+            .iter()
+            .fold(ColourSet::new(), |max, view| max.max(view)),
+        draws: views,
     })
 }
 
@@ -81,57 +149,53 @@ pub fn input_generator(input: &str) -> Input {
 }
 
 #[aoc(day2, part1)]
-pub fn part_1(input: &Input) -> u32 {
-    input
-        .games
-        .iter()
-        .filter_map(
-            |&Game {
-                 max_colours: (r, g, b),
-                 id,
-                 ..
-             }| {
-                match r <= 12 && g <= 13 && b <= 14 {
-                    true => Some(id),
-                    _ => None,
-                }
-            },
-        )
-        .sum()
+pub fn part_1(input: &Input) -> Option<u32> {
+    bag::sum_possible(input, &bag::puzzle())
 }
 
+/// Each game's power multiplies the puzzle's colours and any others it showed,
+/// so a game missing one of the puzzle's colours has no power, but colours
+/// only seen in other games don't matter.
 #[aoc(day2, part2)]
-pub fn part_2(input: &Input) -> u32 {
+pub fn part_2(input: &Input) -> Option<u32> {
+    input.games.iter().try_fold(0u32, |total, game| {
+        let palette: BTreeSet<&str> = game.max_colours.colours().chain(PUZZLE_COLOURS).collect();
+        total.checked_add(game.max_colours.power(palette)?)
+    })
+}
+
+/// The fewest cubes of each colour every game could have been played with,
+/// and the first draw that rules out the part 1 bag.
+pub fn explain(input: &Input) -> String {
+    let puzzle_bag = bag::puzzle();
     input
         .games
         .iter()
-        .map(
-            |&Game {
-                 max_colours: (r, g, b),
-                 ..
-             }| r * g * b,
-        )
-        .sum()
+        .map(|game| {
+            let impossible = match game.first_impossible_draw(&puzzle_bag) {
+                Some(draw) => format!(", impossible from draw {}", draw + 1),
+                None => String::new(),
+            };
+            format!("Game {}: {}{impossible}\n", game.id, game.max_colours)
+        })
+        .collect()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use indoc::indoc;
+    use proptest::prelude::*;
 
     #[test]
     fn test() {
-        let input = input_generator(indoc! {
-            "
-            Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
-            Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
-            Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
-            Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
-            Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green
-            "
-        });
-        assert_eq!(part_1(&input), 8);
-        assert_eq!(part_2(&input), 2286);
+        let raw_input = EXAMPLE;
+        let input = input_generator(raw_input);
+        assert_eq!(part_1(&input), Some(8));
+        assert_eq!(part_2(&input), Some(2286));
+        assert_eq!(reference::part_1(raw_input), 8);
+        assert_eq!(reference::part_2(raw_input), 2286);
+        insta::assert_snapshot!("explain", explain(&input));
     }
 
     #[yare::parameterized(
@@ -144,15 +208,35 @@ mod tests {
     }
 
     #[yare::parameterized(
-        just_red = { "1 red", (1, 0, 0) },
-        just_green = { "1 green", (0, 1, 0) },
-        just_blue = { "1 blue", (0, 0, 1) },
-        two_colours = { "1 blue, 1 red", (1, 0, 1) },
-        three_colours = { "1 blue, 1 green, 2 red", (2, 1, 1) },
+        just_red = { "1 red", &[("red", 1)] },
+        just_green = { "1 green", &[("green", 1)] },
+        just_blue = { "1 blue", &[("blue", 1)] },
+        two_colours = { "1 blue, 1 red", &[("red", 1), ("blue", 1)] },
+        three_colours = { "1 blue, 1 green, 2 red", &[("red", 2), ("green", 1), ("blue", 1)] },
+        other_colours = { "3 yellow, 1 Purple", &[("yellow", 3), ("purple", 1)] },
+        repeated_colour = { "1 red, 2 red", &[("red", 3)] },
     )]
-    fn game_view_parser(input: &str, expected_view: Colours) {
+    fn game_view_parser(input: &str, expected_view: &[(&str, u32)]) {
         let (_, view) = game_view(input).unwrap();
-        assert_eq!(view, expected_view);
+        assert_eq!(view, expected_view.iter().copied().collect());
+    }
+
+    #[yare::parameterized(
+        repeated_colour = { "4294967295 red, 1 red" },
+        huge_count = { "4294967296 red" },
+    )]
+    fn game_view_overflow(input: &str) {
+        assert!(game_view(input).is_err());
+    }
+
+    #[yare::parameterized(
+        big_ids = { "Game 4294967295: 1 red\nGame 1: 1 red", None, Some(0) },
+        big_power = { "Game 1: 65536 red, 65536 green, 1 blue", Some(0), None },
+    )]
+    fn answer_overflow(input: &str, expected_part_1: Option<u32>, expected_part_2: Option<u32>) {
+        let input = input_generator(input);
+        assert_eq!(part_1(&input), expected_part_1);
+        assert_eq!(part_2(&input), expected_part_2);
     }
 
     #[yare::parameterized(
@@ -162,8 +246,90 @@ mod tests {
         game_4 = { "Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red", (14, 3, 15) } ,
         game_5 = { "Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green", (6, 3, 2) } ,
     )]
-    fn minimum_colours(input_line: &str, expected_mins: Colours) {
+    fn minimum_colours(input_line: &str, expected_mins: (u32, u32, u32)) {
+        let (r, g, b) = expected_mins;
         let game = parse_game_line(input_line).unwrap();
-        assert_eq!(game.max_colours, expected_mins);
+        assert_eq!(
+            game.max_colours,
+            ColourSet::new()
+                .with("red", r)
+                .with("green", g)
+                .with("blue", b)
+        );
+    }
+
+    #[test]
+    fn other_colours() {
+        let input = input_generator(indoc! {
+            "
+            Game 1: 2 yellow, 3 purple; 1 red
+            Game 2: 20 purple
+            Game 3: 1 red, 1 green, 1 blue
+            Game 4: 2 yellow, 1 red; 2 green, 3 blue
+            "
+        });
+        assert_eq!(
+            input.palette(),
+            BTreeSet::from(["blue", "green", "purple", "red", "yellow"])
+        );
+        assert_eq!(part_1(&input), Some(3));
+        assert_eq!(part_2(&input), Some(1 + 2 * 2 * 3));
+    }
+
+    #[yare::parameterized(
+        possible = { "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green", &[] },
+        one_draw = { "Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red", &[0] },
+        last_draw = { "Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red", &[2] },
+        every_draw = { "Game 6: 13 red; 1 yellow; 14 green", &[0, 1, 2] },
+    )]
+    fn impossible_draws(input_line: &str, expected: &[usize]) {
+        let game = parse_game_line(input_line).unwrap();
+        let bag = bag::puzzle();
+        assert_eq!(game.impossible_draws(&bag).collect_vec(), expected);
+        assert_eq!(game.first_impossible_draw(&bag), expected.first().copied());
+        for (index, draw) in game.draws().iter().enumerate() {
+            assert_eq!(draw.fits_within(&bag), !expected.contains(&index));
+        }
+    }
+
+    #[test]
+    fn draws_keep_their_order() {
+        let game =
+            parse_game_line("Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue")
+                .unwrap();
+        assert_eq!(
+            game.draws(),
+            [
+                ColourSet::new().with("blue", 1).with("green", 2),
+                ColourSet::new()
+                    .with("green", 3)
+                    .with("blue", 4)
+                    .with("red", 1),
+                ColourSet::new().with("green", 1).with("blue", 1),
+            ]
+        );
+        assert_eq!(
+            game.mean_shown(),
+            BTreeMap::from([("blue", 2.0), ("green", 2.0), ("red", 1.0 / 3.0)])
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn matches_reference(input in strategies::document(1..50, 8, 20)) {
+            prop_assert_eq!(part_1(&input_generator(&input)), Some(reference::part_1(&input)));
+            prop_assert_eq!(part_2(&input_generator(&input)), Some(reference::part_2(&input)));
+        }
+
+        #[test]
+        fn draws_make_up_the_game(input in strategies::document(1..20, 8, 20)) {
+            let bag = bag::puzzle();
+            let input = input_generator(&input);
+            for game in &input.games {
+                let max = game.draws().iter().fold(ColourSet::new(), |max, draw| max.max(draw));
+                prop_assert_eq!(&max, game.max_colours());
+                prop_assert_eq!(game.first_impossible_draw(&bag).is_none(), max.fits_within(&bag));
+            }
+        }
     }
 }
@@ -0,0 +1,36 @@
+//! Slow but straightforward solvers used to cross-check the optimized ones.
+
+/// The largest number of red, green and blue cubes shown in any single draw.
+fn parse_game(line: &str) -> Option<(u32, [u32; 3])> {
+    let (game, draws) = line.split_once(": ")?;
+    let id = game.strip_prefix("Game ")?.parse().ok()?;
+    let mut maximums = [0; 3];
+    for draw in draws.split("; ") {
+        for cubes in draw.split(", ") {
+            let (count, colour) = cubes.split_once(' ')?;
+            let count: u32 = count.parse().ok()?;
+            let index = ["red", "green", "blue"]
+                .iter()
+                .position(|&name| name == colour)?;
+            maximums[index] = maximums[index].max(count);
+        }
+    }
+    Some((id, maximums))
+}
+
+pub fn part_1(input: &str) -> u32 {
+    input
+        .lines()
+        .filter_map(parse_game)
+        .filter(|(_, [red, green, blue])| *red <= 12 && *green <= 13 && *blue <= 14)
+        .map(|(id, _)| id)
+        .sum()
+}
+
+pub fn part_2(input: &str) -> u32 {
+    input
+        .lines()
+        .filter_map(parse_game)
+        .map(|(_, [red, green, blue])| red * green * blue)
+        .sum()
+}
@@ -0,0 +1,281 @@
+//! How likely the recorded games are for a given bag.
+//!
+//! Each draw is modelled as the Elf grabbing as many cubes as they showed,
+//! uniformly at random and without replacement, then putting them back before
+//! the next draw. A draw is then a multivariate hypergeometric sample, so its
+//! likelihood has a closed form that the simulation can be checked against.
+
+use itertools::Itertools;
+
+use super::{colours::ColourSet, Game, Input};
+use crate::rng::Rng;
+
+/// `size` cubes taken from `bag` without replacement, or `None` if the bag
+/// doesn't hold that many.
+pub fn sample_draw(rng: &mut Rng, bag: &ColourSet, size: u32) -> Option<ColourSet> {
+    let total = bag.total()?;
+    if size > total {
+        return None;
+    }
+    let mut remaining = bag.clone();
+    let mut drawn = ColourSet::new();
+    for taken in 0..size {
+        let mut pick = rng.below(u64::from(total - taken)) as u32;
+        let mut picked = None;
+        for (colour, count) in remaining.iter() {
+            if pick < count {
+                picked = Some((colour.to_string(), count));
+                break;
+            }
+            pick -= count;
+        }
+        let (colour, count) = picked?;
+        remaining.set(colour.as_str(), count - 1);
+        drawn.set(colour.as_str(), drawn.get(&colour) + 1);
+    }
+    Some(drawn)
+}
+
+/// The natural log of the number of ways to choose `k` things from `n`.
+fn ln_choose(n: u32, k: u32) -> f64 {
+    (0..k.min(n - k))
+        .map(|i| f64::from(n - i).ln() - f64::from(i + 1).ln())
+        .sum()
+}
+
+/// The log likelihood of `bag` producing exactly `draw`, which is negative
+/// infinity if it can't.
+pub fn draw_log_likelihood(draw: &ColourSet, bag: &ColourSet) -> f64 {
+    let (Some(shown), Some(total)) = (draw.total(), bag.total()) else {
+        return f64::NEG_INFINITY;
+    };
+    if !draw.fits_within(bag) {
+        return f64::NEG_INFINITY;
+    }
+    draw.iter()
+        .map(|(colour, count)| ln_choose(bag.get(colour), count))
+        .sum::<f64>()
+        - ln_choose(total, shown)
+}
+
+/// The log likelihood of `bag` producing every draw of `game`.
+pub fn log_likelihood(game: &Game, bag: &ColourSet) -> f64 {
+    game.draws
+        .iter()
+        .map(|draw| draw_log_likelihood(draw, bag))
+        .sum()
+}
+
+/// The fraction of `trials` simulated games, each with draws the same sizes as
+/// `game`'s, that showed exactly what `game` recorded, or `0.0` if there are
+/// no trials.
+pub fn estimate_likelihood(rng: &mut Rng, game: &Game, bag: &ColourSet, trials: u32) -> f64 {
+    if trials == 0 {
+        return 0.0;
+    }
+    let reproduced = (0..trials)
+        .filter(|_| {
+            game.draws.iter().all(|draw| {
+                let size = draw.total().unwrap_or(u32::MAX);
+                sample_draw(rng, bag, size).as_ref() == Some(draw)
+            })
+        })
+        .count();
+    reproduced as f64 / f64::from(trials)
+}
+
+/// The log likelihood of `bag` producing every game in the input.
+pub fn total_log_likelihood(input: &Input, bag: &ColourSet) -> f64 {
+    input
+        .games
+        .iter()
+        .map(|game| log_likelihood(game, bag))
+        .sum()
+}
+
+/// The bag, with at most `max_count` cubes of any colour, under which all the
+/// games are most likely, preferring fewer cubes on a tie.
+///
+/// A draw's log likelihood is a sum of a term for each colour shown, which
+/// only depends on that colour's count, less a term that only depends on the
+/// bag's total. So this finds the best split of every possible total one
+/// colour at a time, then the total whose best split wins. Colours that were
+/// never shown are left out, since they only make draws less likely.
+pub fn most_likely_bag(input: &Input, max_count: u32) -> ColourSet {
+    let draws = input.games.iter().flat_map(|game| &game.draws);
+    let smallest = draws
+        .clone()
+        .fold(ColourSet::new(), |bag, draw| bag.max(draw));
+
+    // the best bag of each total made from the colours so far, by index
+    let mut best: Vec<Option<(f64, ColourSet)>> = vec![Some((0.0, ColourSet::new()))];
+    for (colour, least) in smallest.iter() {
+        let terms = (least..=max_count.max(least))
+            .map(|count| {
+                let term = draws
+                    .clone()
+                    .map(|draw| ln_choose(count, draw.get(colour)))
+                    .sum::<f64>();
+                (count, term)
+            })
+            .collect_vec();
+        let mut next = vec![None; best.len() + max_count.max(least) as usize];
+        for (total, (likelihood, bag)) in best
+            .iter()
+            .enumerate()
+            .filter_map(|(total, entry)| Some((total, entry.as_ref()?)))
+        {
+            for &(count, term) in &terms {
+                let slot: &mut Option<(f64, ColourSet)> = &mut next[total + count as usize];
+                if slot
+                    .as_ref()
+                    .is_none_or(|(best, _)| likelihood + term > *best)
+                {
+                    *slot = Some((likelihood + term, bag.clone().with(colour, count)));
+                }
+            }
+        }
+        best = next;
+    }
+
+    best.into_iter()
+        .enumerate()
+        .filter_map(|(total, entry)| {
+            let (likelihood, bag) = entry?;
+            let total = total as u32;
+            let shown = draws
+                .clone()
+                .map(|draw| ln_choose(total, draw.total().unwrap_or(total)))
+                .sum::<f64>();
+            Some((likelihood - shown, bag))
+        })
+        .reduce(|best, candidate| {
+            if candidate.0 > best.0 {
+                candidate
+            } else {
+                best
+            }
+        })
+        .map_or_else(ColourSet::new, |(_, bag)| bag)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day_02::{input_generator, parse_game_line};
+    use indoc::indoc;
+
+    fn bag(spec: &str) -> ColourSet {
+        spec.parse().unwrap()
+    }
+
+    #[yare::parameterized(
+        some = { "12 red, 13 green, 14 blue", 10 },
+        everything = { "2 red, 1 blue", 3 },
+        nothing = { "2 red, 1 blue", 0 },
+    )]
+    fn samples_fit_the_bag(spec: &str, size: u32) {
+        let bag = bag(spec);
+        let mut rng = Rng::new(3);
+        for _ in 0..100 {
+            let draw = sample_draw(&mut rng, &bag, size).unwrap();
+            assert_eq!(draw.total(), Some(size));
+            assert!(draw.fits_within(&bag));
+        }
+        assert_eq!(sample_draw(&mut rng, &bag, bag.total().unwrap() + 1), None);
+    }
+
+    #[yare::parameterized(
+        certain = { "2 red", "2 red", 1.0 },
+        one_of_each = { "1 red, 1 blue", "2 red, 1 blue", 2.0 / 3.0 },
+        both_red = { "2 red", "2 red, 1 blue", 1.0 / 3.0 },
+        too_many = { "3 red", "2 red, 1 blue", 0.0 },
+        unknown_colour = { "1 yellow", "2 red, 1 blue", 0.0 },
+    )]
+    fn draw_likelihood(draw: &str, spec: &str, expected: f64) {
+        let likelihood = draw_log_likelihood(&bag(draw), &bag(spec)).exp();
+        assert!((likelihood - expected).abs() < 1e-9, "{likelihood}");
+    }
+
+    #[yare::parameterized(
+        one_draw = { "Game 1: 1 red, 1 blue", "2 red, 1 blue" },
+        two_draws = { "Game 1: 1 red, 1 blue; 2 red", "3 red, 2 blue" },
+        puzzle = { "Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green", "12 red, 13 green, 14 blue" },
+    )]
+    fn simulation_matches_likelihood(game: &str, spec: &str) {
+        let game = parse_game_line(game).unwrap();
+        let bag = bag(spec);
+        let exact = log_likelihood(&game, &bag).exp();
+        let estimate = estimate_likelihood(&mut Rng::new(11), &game, &bag, 20_000);
+        assert!((estimate - exact).abs() < 0.01, "{estimate} vs {exact}");
+    }
+
+    #[test]
+    fn no_trials() {
+        let game = parse_game_line("Game 1: 1 red").unwrap();
+        let estimate = estimate_likelihood(&mut Rng::new(11), &game, &bag("1 red"), 0);
+        assert_eq!(estimate, 0.0);
+    }
+
+    /// Every bag with between the fewest cubes each colour needs and
+    /// `max_count`.
+    fn bags(input: &Input, max_count: u32) -> Vec<ColourSet> {
+        let smallest = input
+            .games
+            .iter()
+            .fold(ColourSet::new(), |bag, game| bag.max(&game.max_colours));
+        smallest
+            .iter()
+            .map(|(colour, least)| (least..=max_count).map(move |count| (colour, count)))
+            .multi_cartesian_product()
+            .map(|counts| counts.into_iter().collect())
+            .collect()
+    }
+
+    #[test]
+    fn most_likely_bag_beats_every_other_bag() {
+        let truth = bag("4 red, 5 green, 3 blue");
+        let mut rng = Rng::new(5);
+        let games = (1..=20)
+            .map(|id| {
+                let draws = (0..3)
+                    .map(|_| {
+                        let size = rng.between(1, 6);
+                        sample_draw(&mut rng, &truth, size).unwrap().to_string()
+                    })
+                    .join("; ");
+                format!("Game {id}: {draws}\n")
+            })
+            .collect::<String>();
+        let input = input_generator(&games);
+
+        let estimate = most_likely_bag(&input, 9);
+        let best = total_log_likelihood(&input, &estimate);
+        for other in bags(&input, 9) {
+            assert!(
+                total_log_likelihood(&input, &other) <= best + 1e-9,
+                "{other}"
+            );
+        }
+    }
+
+    /// Changing any one colour makes these less likely, so a search that only
+    /// did that would stop at the smallest bag.
+    #[test]
+    fn scales_every_colour_together() {
+        let input = input_generator(indoc! {
+            "
+            Game 1: 2 red
+            Game 2: 2 blue
+            Game 3: 1 red, 1 blue
+            "
+        });
+        assert_eq!(most_likely_bag(&input, 10), bag("10 red, 10 blue"));
+    }
+
+    #[test]
+    fn ties_prefer_fewer_cubes() {
+        let input = input_generator("Game 1: 2 red; 1 red");
+        assert_eq!(most_likely_bag(&input, 10), bag("2 red"));
+    }
+}
@@ -0,0 +1,39 @@
+//! Proptest strategies that build game records draw by draw, so a failing
+//! case shrinks to the fewest games, draws and cubes that still fail.
+
+use std::ops::Range;
+
+use itertools::Itertools;
+use proptest::{collection::vec, prelude::*, sample::subsequence};
+
+use super::PUZZLE_COLOURS;
+
+/// A non-empty selection of the puzzle's colours in any order, each shown
+/// between 1 and `max_cubes` times.
+fn draw(max_cubes: u32) -> impl Strategy<Value = String> {
+    let colours = subsequence(PUZZLE_COLOURS.to_vec(), 1..=PUZZLE_COLOURS.len()).prop_shuffle();
+    let counts = vec(1..=max_cubes, PUZZLE_COLOURS.len());
+    (colours, counts).prop_map(|(colours, counts)| {
+        colours
+            .iter()
+            .zip(counts)
+            .map(|(colour, count)| format!("{count} {colour}"))
+            .join(", ")
+    })
+}
+
+/// A record of `games` games numbered from 1, each with between 1 and
+/// `max_draws` draws.
+pub fn document(
+    games: Range<usize>,
+    max_draws: usize,
+    max_cubes: u32,
+) -> impl Strategy<Value = String> {
+    vec(vec(draw(max_cubes), 1..=max_draws), games).prop_map(|games| {
+        games
+            .iter()
+            .enumerate()
+            .map(|(index, draws)| format!("Game {}: {}\n", index + 1, draws.join("; ")))
+            .collect()
+    })
+}
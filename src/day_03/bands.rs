@@ -0,0 +1,145 @@
+//! Both parts worked out over horizontal bands of the schematic, which can be
+//! scanned on separate threads.
+
+use std::ops::Range;
+
+use rayon::prelude::*;
+
+use super::{
+    gears::GearRule,
+    schematic::Options,
+    stream::{scan, Row},
+};
+use crate::grid::{
+    point::{Direction, Point},
+    Grid,
+};
+
+/// How to split a schematic into bands. The default scans bands of 64 rows in
+/// parallel.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Bands {
+    /// The rows each band is responsible for, at least one.
+    pub height: usize,
+    pub parallel: bool,
+}
+
+impl Default for Bands {
+    fn default() -> Self {
+        Bands {
+            height: 64,
+            parallel: true,
+        }
+    }
+}
+
+/// Both totals for the rows in `band` of `grid`. The rows either side of the
+/// band are read too, but only to see what the band's own rows touch, so a
+/// number or gear on a border is counted by the one band it belongs to.
+fn scan_band(grid: &Grid<u8>, mut band: Range<usize>, rule: &GearRule) -> Option<(u32, u32)> {
+    let first = band.start.saturating_sub(1);
+    let rows = (first..(band.end + 1).min(grid.height()))
+        .map(|row| Row::new(row, grid.row(row)?).ok())
+        .collect::<Option<Vec<_>>>()?;
+    // every row read is at or below the first one
+    let row_at = |point: Point| rows.get(point.y - first);
+    band.try_fold((0u32, 0u32), |(parts, ratios), row| {
+        let point = Point::new(0, row);
+        let window = [
+            point.step(Direction::N).and_then(row_at),
+            row_at(point),
+            point.step(Direction::S).and_then(row_at),
+        ];
+        let (row_parts, row_ratios) = scan(window, rule)?;
+        Some((
+            parts.checked_add(row_parts)?,
+            ratios.checked_add(row_ratios)?,
+        ))
+    })
+}
+
+/// Both parts for the schematic in `input`, with gears found by `rule`, the
+/// same as [`super::part_1`] and [`super::part_2`] give for the puzzle's rule.
+pub fn solve(input: &str, rule: &GearRule, bands: &Bands) -> Option<(u32, u32)> {
+    let grid = Grid::parse_with(input, Options::default().policy).ok()?;
+    let height = bands.height.max(1);
+    let starts = (0..grid.height()).step_by(height).collect::<Vec<_>>();
+    let band = |start: usize| scan_band(&grid, start..(start + height).min(grid.height()), rule);
+    let totals = if bands.parallel {
+        starts
+            .into_par_iter()
+            .map(band)
+            .collect::<Option<Vec<_>>>()?
+    } else {
+        starts.into_iter().map(band).collect::<Option<Vec<_>>>()?
+    };
+    totals.into_iter().try_fold(
+        (0u32, 0u32),
+        |(parts, ratios), (band_parts, band_ratios)| {
+            Some((
+                parts.checked_add(band_parts)?,
+                ratios.checked_add(band_ratios)?,
+            ))
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day_03::{part_1, part_2, strategies, EXAMPLE};
+    use proptest::prelude::*;
+
+    fn sequential(input: &str) -> Option<(u32, u32)> {
+        part_1(input).zip(part_2(input))
+    }
+
+    #[yare::parameterized(
+        one_row_bands = { 1 },
+        two_row_bands = { 2 },
+        uneven = { 3 },
+        whole = { 10 },
+        taller_than_schematic = { 100 },
+        zero_treated_as_one = { 0 },
+    )]
+    fn example(height: usize) {
+        let input = EXAMPLE;
+        let crlf = input.replace('\n', "\r\n");
+        for parallel in [false, true] {
+            let bands = Bands { height, parallel };
+            assert_eq!(solve(input, &GearRule::new(), &bands), Some((4361, 467835)));
+            assert_eq!(solve(&crlf, &GearRule::new(), &bands), Some((4361, 467835)));
+        }
+    }
+
+    #[yare::parameterized(
+        symbol_in_next_band = { "12...\n..#..\n...34\n" },
+        gear_on_border = { "12...\n..*..\n...34\n" },
+        ragged = { "12*\n..\n" },
+        too_large = { "...\n4294967296*\n" },
+        empty = { "" },
+    )]
+    fn borders(input: &str) {
+        for height in 1..=3 {
+            let bands = Bands {
+                height,
+                ..Default::default()
+            };
+            assert_eq!(solve(input, &GearRule::new(), &bands), sequential(input));
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn matches_sequential(
+            input in strategies::schematic(1..40, 1..60),
+            height in 1..20usize,
+        ) {
+            let bands = Bands {
+                height,
+                ..Default::default()
+            };
+            prop_assert_eq!(solve(&input, &GearRule::new(), &bands), sequential(&input));
+        }
+    }
+}
@@ -0,0 +1,149 @@
+//! Which symbols count as gears and what their ratio is.
+
+use super::schematic::{Number, Schematic};
+
+/// How many numbers a gear has to touch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Count {
+    Exactly(usize),
+    AtLeast(usize),
+    AtMost(usize),
+}
+
+impl Count {
+    pub fn allows(self, numbers: usize) -> bool {
+        match self {
+            Count::Exactly(count) => numbers == count,
+            Count::AtLeast(count) => numbers >= count,
+            Count::AtMost(count) => numbers <= count,
+        }
+    }
+}
+
+/// How the numbers touching a gear combine into its ratio.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Aggregate {
+    Product,
+    Sum,
+    Max,
+}
+
+impl Aggregate {
+    /// The combined values, which is `0` if there are none, or `None` if it
+    /// doesn't fit in a `u32`.
+    pub fn apply(self, mut values: impl Iterator<Item = u32>) -> Option<u32> {
+        let Some(first) = values.next() else {
+            return Some(0);
+        };
+        values.try_fold(first, |total, value| match self {
+            Aggregate::Product => total.checked_mul(value),
+            Aggregate::Sum => total.checked_add(value),
+            Aggregate::Max => Some(total.max(value)),
+        })
+    }
+}
+
+/// The symbols that can be gears, how many numbers they need and how their
+/// ratio is found. The default is the puzzle's: a `*` touching exactly two
+/// numbers, multiplied together.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GearRule {
+    /// `None` for any symbol.
+    symbols: Option<Vec<u8>>,
+    count: Count,
+    aggregate: Aggregate,
+}
+
+impl Default for GearRule {
+    fn default() -> Self {
+        GearRule {
+            symbols: Some(vec![b'*']),
+            count: Count::Exactly(2),
+            aggregate: Aggregate::Product,
+        }
+    }
+}
+
+impl GearRule {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn symbols(mut self, symbols: &[u8]) -> Self {
+        self.symbols = Some(symbols.to_vec());
+        self
+    }
+
+    pub fn any_symbol(mut self) -> Self {
+        self.symbols = None;
+        self
+    }
+
+    pub fn count(mut self, count: Count) -> Self {
+        self.count = count;
+        self
+    }
+
+    pub fn aggregate(mut self, aggregate: Aggregate) -> Self {
+        self.aggregate = aggregate;
+        self
+    }
+
+    /// Whether `symbol` touching this many numbers is a gear.
+    pub fn is_gear(&self, symbol: u8, numbers: usize) -> bool {
+        self.symbols
+            .as_ref()
+            .is_none_or(|symbols| symbols.contains(&symbol))
+            && self.count.allows(numbers)
+    }
+
+    pub fn ratio(&self, numbers: &[&Number]) -> Option<u32> {
+        self.aggregate
+            .apply(numbers.iter().map(|number| number.value))
+    }
+
+    /// The sum of the ratios of every gear in `schematic`.
+    pub fn total(&self, schematic: &Schematic) -> Option<u32> {
+        schematic.gears(self).try_fold(0u32, |total, (_, numbers)| {
+            total.checked_add(self.ratio(&numbers)?)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day_03::EXAMPLE;
+
+    #[yare::parameterized(
+        puzzle = { GearRule::new(), Some(467835) },
+        triple = { GearRule::new().count(Count::Exactly(3)), Some(0) },
+        any_pair_summed = {
+            GearRule::new().any_symbol().count(Count::AtLeast(2)).aggregate(Aggregate::Sum),
+            Some(502 + 1353),
+        },
+        lonely_stars = { GearRule::new().count(Count::Exactly(1)).aggregate(Aggregate::Max), Some(617) },
+        singles = { GearRule::new().any_symbol().count(Count::AtMost(1)), Some(633 + 617 + 592 + 664) },
+        largest = {
+            GearRule::new().symbols(b"*$").count(Count::AtLeast(1)).aggregate(Aggregate::Max),
+            Some(467 + 617 + 664 + 755),
+        },
+    )]
+    fn rules(rule: GearRule, expected: Option<u32>) {
+        let schematic = Schematic::parse(EXAMPLE).unwrap();
+        assert_eq!(rule.total(&schematic), expected);
+    }
+
+    #[yare::parameterized(
+        empty_product = { Aggregate::Product, &[], Some(0) },
+        product = { Aggregate::Product, &[2, 3, 4], Some(24) },
+        sum = { Aggregate::Sum, &[2, 3, 4], Some(9) },
+        max = { Aggregate::Max, &[2, 4, 3], Some(4) },
+        product_overflow = { Aggregate::Product, &[65536, 65536], None },
+        sum_overflow = { Aggregate::Sum, &[u32::MAX, 1], None },
+        max_of_large = { Aggregate::Max, &[u32::MAX, 1], Some(u32::MAX) },
+    )]
+    fn aggregate(aggregate: Aggregate, values: &[u32], expected: Option<u32>) {
+        assert_eq!(aggregate.apply(values.iter().copied()), expected);
+    }
+}
@@ -0,0 +1,109 @@
+//! Random engine schematics for stress testing.
+
+use crate::rng::Rng;
+
+#[derive(Debug, Clone)]
+pub struct Config {
+    pub width: usize,
+    pub height: usize,
+    /// How likely a number is to start at any free cell.
+    pub number_density: f64,
+    /// How likely a symbol is to be placed at any free cell.
+    pub symbol_density: f64,
+    pub symbols: Vec<u8>,
+    /// Whether a number may touch more than one symbol, which never happens in
+    /// real puzzle inputs.
+    pub shared_numbers: bool,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            width: 140,
+            height: 140,
+            number_density: 0.1,
+            symbol_density: 0.05,
+            symbols: b"*#+$/@=%&-".to_vec(),
+            shared_numbers: false,
+        }
+    }
+}
+
+fn row(rng: &mut Rng, config: &Config) -> Vec<u8> {
+    let mut row = vec![b'.'; config.width];
+    let mut col = 0;
+    while col < config.width {
+        if rng.chance(config.number_density) {
+            let digits = rng.between(1, 3).min((config.width - col) as u32);
+            let number = rng.between(10u32.pow(digits - 1), 10u32.pow(digits) - 1);
+            row[col..col + digits as usize].copy_from_slice(number.to_string().as_bytes());
+            // always leave a non-digit after a number so it doesn't run on
+            col += digits as usize + 1;
+        } else {
+            if rng.chance(config.symbol_density) {
+                row[col] = *rng.choose(&config.symbols);
+            }
+            col += 1;
+        }
+    }
+    row
+}
+
+fn is_symbol(c: u8) -> bool {
+    !matches!(c, b'0'..=b'9' | b'.')
+}
+
+/// Clears every symbol but the first around each number.
+fn unshare_numbers(rows: &mut [Vec<u8>]) {
+    for row in 0..rows.len() {
+        let mut col = 0;
+        while col < rows[row].len() {
+            if !rows[row][col].is_ascii_digit() {
+                col += 1;
+                continue;
+            }
+            let start = col;
+            while col < rows[row].len() && rows[row][col].is_ascii_digit() {
+                col += 1;
+            }
+            let mut seen_symbol = false;
+            for y in row.saturating_sub(1)..(row + 2).min(rows.len()) {
+                for x in start.saturating_sub(1)..(col + 1).min(rows[y].len()) {
+                    if is_symbol(rows[y][x]) {
+                        if seen_symbol {
+                            rows[y][x] = b'.';
+                        }
+                        seen_symbol = true;
+                    }
+                }
+            }
+        }
+    }
+}
+
+pub fn generate(rng: &mut Rng, config: &Config) -> String {
+    let mut rows: Vec<Vec<u8>> = (0..config.height).map(|_| row(rng, config)).collect();
+    if !config.shared_numbers {
+        unshare_numbers(&mut rows);
+    }
+    rows.into_iter()
+        .map(|row| String::from_utf8(row).unwrap() + "\n")
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dimensions() {
+        let config = Config {
+            width: 20,
+            height: 10,
+            ..Config::default()
+        };
+        let input = generate(&mut Rng::new(1), &config);
+        assert_eq!(input.lines().count(), 10);
+        assert!(input.lines().all(|line| line.len() == 20));
+    }
+}
@@ -1,100 +1,56 @@
-use itertools::Itertools;
+pub mod bands;
+pub mod gears;
+pub mod generate;
+pub mod reference;
+pub mod render;
+pub mod schematic;
+#[cfg(test)]
+mod strategies;
+pub mod stream;
+
+use gears::GearRule;
+use schematic::{Options, Schematic};
+
+/// The example schematic from the puzzle.
+#[cfg(test)]
+pub(crate) const EXAMPLE: &str = indoc::indoc! {
+    "
+    467..114..
+    ...*......
+    ..35..633.
+    ......#...
+    617*......
+    .....+.58.
+    ..592.....
+    ......755.
+    ...$.*....
+    .664.598..
+    "
+};
 
 fn is_symbol(c: u8) -> bool {
     !matches!(c, b'0'..=b'9' | b'.')
 }
 
-fn check_for_numbers<Data: Clone>(
-    line_bytes: &[u8],
-    starting_index: usize,
-    data: &mut Data,
-    number_operation: &mut impl FnMut(u32, &mut Data),
-) {
-    match line_bytes.get(starting_index).map(|c| c.is_ascii_digit()) {
-        Some(true) => {
-            let number = scan_in_number(line_bytes, starting_index);
-            number_operation(number, data);
-        }
-        Some(false) => {
-            let number = scan_in_number(line_bytes, starting_index + 1);
-            number_operation(number, data);
-            if let Some(index) = starting_index.checked_sub(1) {
-                let number = scan_in_number(line_bytes, index);
-                number_operation(number, data);
-            }
-        }
-        _ => {}
-    }
-}
-
-fn part_x<Data: Clone>(
-    input: &str,
-    is_relevant: impl Fn(u8) -> bool,
-    starting_data: Data,
-    mut number_operation: impl FnMut(u32, &mut Data),
-    total_operation: impl Fn(Data) -> u32,
-) -> u32 {
-    let lines = input.lines().collect_vec();
-    let mut total = 0;
-    for (line_index, line) in lines.iter().enumerate() {
-        let line_bytes = line.as_bytes();
-        for (char_index, char) in line.bytes().enumerate() {
-            if !is_relevant(char) {
-                continue;
-            }
-
-            let mut data = starting_data.clone();
-
-            if char_index
-                .checked_sub(1)
-                .and_then(|i| line_bytes.get(i))
-                .map(|b| b.is_ascii_digit())
-                .unwrap_or(false)
-            {
-                let number = scan_in_number(line_bytes, char_index - 1);
-                number_operation(number, &mut data);
-            }
-
-            if char_index
-                .checked_add(1)
-                .and_then(|i| line_bytes.get(i))
-                .map(|b| b.is_ascii_digit())
-                .unwrap_or(false)
-            {
-                let number = scan_in_number(line_bytes, char_index + 1);
-                number_operation(number, &mut data);
-            }
-
-            if let Some(line_bytes) = line_index
-                .checked_sub(1)
-                .and_then(|i| lines.get(i))
-                .map(|line| line.as_bytes())
-            {
-                check_for_numbers(line_bytes, char_index, &mut data, &mut number_operation);
-            }
-            if let Some(line_bytes) = line_index
-                .checked_add(1)
-                .and_then(|i| lines.get(i))
-                .map(|line| line.as_bytes())
-            {
-                check_for_numbers(line_bytes, char_index, &mut data, &mut number_operation);
-            }
-            total += total_operation(data);
-        }
-    }
-    total
+fn part_number_total(schematic: &Schematic) -> Option<u32> {
+    schematic
+        .part_numbers()
+        .try_fold(0u32, |total, number| total.checked_add(number.value))
 }
 
+/// Counts each part number once, however many symbols it touches.
 #[aoc(day3, part1)]
-pub fn part_1(input: &str) -> u32 {
-    part_x(input, is_symbol, 0, |num, t| *t += num, |t| t)
+pub fn part_1(input: &str) -> Option<u32> {
+    part_number_total(&Schematic::parse(input)?)
 }
 
-fn scan_in_number(line_bytes: &[u8], starting_index: usize) -> u32 {
+/// The number whose digits include `starting_index`, `0` if there is no digit
+/// there, or `None` if the number doesn't fit in a `u32`.
+pub fn scan_in_number(line_bytes: &[u8], starting_index: usize) -> Option<u32> {
     let mut c = starting_index;
-    let mut number_value = 0;
+    let mut number_value: u32 = 0;
     if let Some(false) = line_bytes.get(c).map(|c| c.is_ascii_digit()) {
-        return 0;
+        return Some(0);
     }
     loop {
         match line_bytes.get(c).map(|c| c.is_ascii_digit()) {
@@ -113,57 +69,51 @@ fn scan_in_number(line_bytes: &[u8], starting_index: usize) -> u32 {
     }
     while let Some((char, true)) = line_bytes.get(c).map(|c| (c, c.is_ascii_digit())) {
         c += 1;
-        number_value *= 10;
-        number_value += (char - b'0') as u32;
+        number_value = number_value
+            .checked_mul(10)?
+            .checked_add((char - b'0') as u32)?;
     }
-    number_value
+    Some(number_value)
 }
 
 #[aoc(day3, part2)]
-pub fn part_2(input: &str) -> u32 {
-    part_x(
-        input,
-        |c| c == b'*',
-        (0, 1),
-        |num, (c, p)| {
-            if num > 0 {
-                *c += 1;
-                *p *= num;
-            }
-        },
-        |(c, p)| {
-            if c == 2 {
-                p
-            } else {
-                0
-            }
-        },
-    )
+pub fn part_2(input: &str) -> Option<u32> {
+    GearRule::new().total(&Schematic::parse(input)?)
+}
+
+/// Both parts for a variant of the puzzle, with the schematic read using
+/// `options` and gears found by `rule`.
+pub fn solve(input: &str, options: &Options, rule: &GearRule) -> Option<(u32, u32)> {
+    let schematic = Schematic::parse_with(input, options).ok()?;
+    Some((part_number_total(&schematic)?, rule.total(&schematic)?))
+}
+
+/// Every part number, in reading order, or why the input isn't a schematic.
+pub fn explain(input: &str) -> String {
+    match Schematic::parse_with(input, &Options::default()) {
+        Ok(schematic) => schematic
+            .part_numbers()
+            .map(|number| format!("{}\n", number.value))
+            .collect(),
+        Err(error) => format!("invalid schematic: {error}\n"),
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::grid::{Neighbourhood, Policy, Topology};
     use indoc::indoc;
+    use proptest::prelude::*;
 
     #[test]
     fn test() {
-        let input = indoc! {
-            "
-            467..114..
-            ...*......
-            ..35..633.
-            ......#...
-            617*......
-            .....+.58.
-            ..592.....
-            ......755.
-            ...$.*....
-            .664.598..
-            "
-        };
-        assert_eq!(part_1(input), 4361);
-        assert_eq!(part_2(input), 467835);
+        let input = EXAMPLE;
+        assert_eq!(part_1(input), Some(4361));
+        assert_eq!(part_2(input), Some(467835));
+        assert_eq!(reference::part_1(input), 4361);
+        assert_eq!(reference::part_2(input), 467835);
+        insta::assert_snapshot!("explain", explain(input));
     }
 
     #[yare::parameterized(
@@ -307,9 +257,19 @@ mod tests {
             },
             228,
         },
+        shared = {
+            indoc! {
+                "
+                *...
+                .12.
+                ..#.
+                "
+            },
+            12,
+        },
     )]
     fn symbol_test(input: &str, expected_result: u32) {
-        assert_eq!(part_1(input), expected_result);
+        assert_eq!(part_1(input), Some(expected_result));
     }
 
     #[yare::parameterized(
@@ -320,7 +280,7 @@ mod tests {
         spaced_right = { "1.#", 0 },
     )]
     fn no_symbol_test(input: &str, expected_result: u32) {
-        assert_eq!(part_1(input), expected_result);
+        assert_eq!(part_1(input), Some(expected_result));
     }
 
     #[yare::parameterized(
@@ -333,10 +293,59 @@ mod tests {
     fn number_scanning(input: &str, starting_index: usize, expected_result: u32) {
         assert_eq!(
             scan_in_number(input.as_bytes(), starting_index),
-            expected_result
+            Some(expected_result)
         );
     }
 
+    #[yare::parameterized(
+        long_number = { "4294967296*", None, None },
+        gear_product = { "65536*65536", Some(131072), None },
+        total = { "4294967295*\n1*........", None, Some(4294967295) },
+    )]
+    fn overflow(input: &str, expected_part_1: Option<u32>, expected_part_2: Option<u32>) {
+        assert_eq!(part_1(input), expected_part_1);
+        assert_eq!(part_2(input), expected_part_2);
+    }
+
+    #[yare::parameterized(
+        short_line = { "1*\n.", Some(1), Some(0), "1\n" },
+        long_line = { "1*\n2...", Some(3), Some(2), "1\n2\n" },
+        blank_line = { "1*\n\n2*2\n\n", Some(5), Some(4), "1\n2\n2\n" },
+    )]
+    fn ragged(
+        input: &str,
+        expected_part_1: Option<u32>,
+        expected_part_2: Option<u32>,
+        expected_explain: &str,
+    ) {
+        assert_eq!(part_1(input), expected_part_1);
+        assert_eq!(part_2(input), expected_part_2);
+        assert_eq!(explain(input), expected_explain);
+    }
+
+    #[test]
+    fn zero() {
+        // a literal 0 is a part number like any other, so it counts towards a
+        // gear's numbers even though it adds nothing to the total
+        let input = "3*0\n.4.";
+        assert_eq!(part_1(input), Some(7));
+        assert_eq!(part_2(input), Some(0));
+        assert_eq!(explain(input), "3\n0\n4\n");
+    }
+
+    #[test]
+    fn crlf() {
+        let input = "467..114..\r\n...*......\r\n..35..633.\r\n";
+        let strict = Options {
+            policy: Policy::Strict,
+            ..Default::default()
+        };
+        assert_eq!(part_1(input), Some(502));
+        assert_eq!(part_2(input), Some(16345));
+        assert_eq!(solve(input, &strict, &GearRule::new()), Some((502, 16345)));
+        assert_eq!(explain(input), "467\n35\n");
+    }
+
     #[yare::parameterized(
         one_side = {
             indoc! {
@@ -467,6 +476,53 @@ mod tests {
         },
     )]
     fn gear_test(input: &str, expected_result: u32) {
-        assert_eq!(part_2(input), expected_result);
+        assert_eq!(part_2(input), Some(expected_result));
+    }
+
+    #[test]
+    fn variants() {
+        let input = indoc! {
+            "
+            2..*
+            ..
+            ...3
+            "
+        };
+        let options = Options {
+            policy: Policy::Pad(b'.'),
+            neighbourhood: Neighbourhood::VonNeumann,
+            topology: Topology::Toroidal,
+        };
+        let strict = Options {
+            policy: Policy::Strict,
+            ..Default::default()
+        };
+        assert_eq!(solve(input, &strict, &GearRule::new()), None);
+        assert_eq!(
+            solve(input, &Options::default(), &GearRule::new()),
+            Some((0, 0))
+        );
+        assert_eq!(solve(input, &options, &GearRule::new()), Some((5, 6)));
+    }
+
+    proptest! {
+        #[test]
+        fn matches_reference(input in strategies::schematic(1..40, 1..40)) {
+            prop_assert_eq!(part_1(&input), Some(reference::part_1(&input)));
+            prop_assert_eq!(part_2(&input), Some(reference::part_2(&input)));
+        }
+
+        #[test]
+        fn scan_in_number_round_trips(
+            number: u32,
+            prefix in "[^0-9]{0,4}",
+            suffix in "[^0-9]{0,4}",
+            offset: prop::sample::Index,
+        ) {
+            let digits = number.to_string();
+            let line = format!("{prefix}{digits}{suffix}");
+            let index = prefix.len() + offset.index(digits.len());
+            prop_assert_eq!(scan_in_number(line.as_bytes(), index), Some(number));
+        }
     }
 }
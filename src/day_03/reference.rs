@@ -0,0 +1,80 @@
+//! Slow but straightforward solvers used to cross-check the optimized ones.
+
+struct Number {
+    value: u32,
+    row: usize,
+    start: usize,
+    end: usize,
+}
+
+/// Every maximal run of digits in the schematic.
+fn numbers(rows: &[&[u8]]) -> Vec<Number> {
+    let mut numbers = Vec::new();
+    for (row, line) in rows.iter().enumerate() {
+        let mut col = 0;
+        while col < line.len() {
+            if !line[col].is_ascii_digit() {
+                col += 1;
+                continue;
+            }
+            let start = col;
+            while col < line.len() && line[col].is_ascii_digit() {
+                col += 1;
+            }
+            let value = std::str::from_utf8(&line[start..col])
+                .unwrap()
+                .parse()
+                .unwrap();
+            numbers.push(Number {
+                value,
+                row,
+                start,
+                end: col,
+            });
+        }
+    }
+    numbers
+}
+
+/// Whether the cell at (`row`, `col`) touches `number`, diagonals included.
+fn is_adjacent(number: &Number, row: usize, col: usize) -> bool {
+    row + 1 >= number.row && row <= number.row + 1 && col + 1 >= number.start && col <= number.end
+}
+
+fn cells<'a>(rows: &'a [&'a [u8]]) -> impl Iterator<Item = (usize, usize, u8)> + 'a {
+    rows.iter()
+        .enumerate()
+        .flat_map(|(row, line)| line.iter().enumerate().map(move |(col, &c)| (row, col, c)))
+}
+
+pub fn part_1(input: &str) -> u32 {
+    let rows: Vec<&[u8]> = input.lines().map(str::as_bytes).collect();
+    numbers(&rows)
+        .iter()
+        .filter(|number| {
+            cells(&rows).any(|(row, col, c)| {
+                !c.is_ascii_digit() && c != b'.' && is_adjacent(number, row, col)
+            })
+        })
+        .map(|number| number.value)
+        .sum()
+}
+
+pub fn part_2(input: &str) -> u32 {
+    let rows: Vec<&[u8]> = input.lines().map(str::as_bytes).collect();
+    let numbers = numbers(&rows);
+    cells(&rows)
+        .filter(|&(_, _, c)| c == b'*')
+        .map(|(row, col, _)| {
+            let neighbours: Vec<u32> = numbers
+                .iter()
+                .filter(|number| is_adjacent(number, row, col))
+                .map(|number| number.value)
+                .collect();
+            match neighbours[..] {
+                [a, b] => a * b,
+                _ => 0,
+            }
+        })
+        .sum()
+}
@@ -0,0 +1,183 @@
+//! Schematics drawn for a terminal or as an image, with the numbers and gears
+//! picked out.
+
+use std::ops::Range;
+
+use super::{
+    gears::GearRule,
+    schematic::{Schematic, Symbol},
+};
+use crate::grid::{image::Rgb, Grid};
+
+const PART: &str = "\x1b[1;32m";
+const UNATTACHED: &str = "\x1b[2m";
+const GEAR: &str = "\x1b[1;33m";
+const RESET: &str = "\x1b[0m";
+
+/// The rows and columns to draw, which are clamped to the schematic.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Viewport {
+    pub rows: Range<usize>,
+    pub cols: Range<usize>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Style {
+    Plain,
+    Part,
+    Unattached,
+    Gear,
+}
+
+impl Style {
+    fn escape(self) -> &'static str {
+        match self {
+            Style::Plain => "",
+            Style::Part => PART,
+            Style::Unattached => UNATTACHED,
+            Style::Gear => GEAR,
+        }
+    }
+}
+
+/// How each cell should be drawn, and the gears with their ratios.
+fn styles<'a>(
+    schematic: &'a Schematic,
+    rule: &'a GearRule,
+) -> (Grid<Style>, Vec<(&'a Symbol, Option<u32>)>) {
+    let mut styles = schematic.grid().map(|_| Style::Plain);
+    for (index, number) in schematic.numbers().iter().enumerate() {
+        let style = match schematic.symbols_touching(index).next() {
+            Some(_) => Style::Part,
+            None => Style::Unattached,
+        };
+        for cell in number.cells() {
+            styles[cell] = style;
+        }
+    }
+    let mut gears = Vec::new();
+    for (symbol, numbers) in schematic.gears(rule) {
+        styles[symbol.position] = Style::Gear;
+        gears.push((symbol, rule.ratio(&numbers)));
+    }
+    (styles, gears)
+}
+
+/// The schematic with part numbers in green, numbers touching no symbol
+/// dimmed and the gears `rule` finds in yellow, followed on each row by the
+/// ratios of the gears drawn on it. Cells are bytes, so a char that's cut by
+/// the viewport or split by a style is drawn as a replacement character.
+pub fn render(schematic: &Schematic, rule: &GearRule, viewport: Option<&Viewport>) -> String {
+    let grid = schematic.grid();
+    let (styles, gears) = styles(schematic, rule);
+
+    let rows = match viewport {
+        Some(viewport) => viewport.rows.start..viewport.rows.end.min(grid.height()),
+        None => 0..grid.height(),
+    };
+    let cols = match viewport {
+        Some(viewport) => viewport.cols.start..viewport.cols.end.min(grid.width()),
+        None => 0..grid.width(),
+    };
+    let mut rendered = Vec::new();
+    for y in rows {
+        let mut current = Style::Plain;
+        for x in cols.clone() {
+            let style = styles[(x, y)];
+            if style != current {
+                if current != Style::Plain {
+                    rendered.extend_from_slice(RESET.as_bytes());
+                }
+                rendered.extend_from_slice(style.escape().as_bytes());
+                current = style;
+            }
+            rendered.push(grid[(x, y)]);
+        }
+        if current != Style::Plain {
+            rendered.extend_from_slice(RESET.as_bytes());
+        }
+        for (_, ratio) in gears
+            .iter()
+            .filter(|(symbol, _)| symbol.position.y == y && cols.contains(&symbol.position.x))
+        {
+            let ratio = ratio.map_or_else(|| "too large".to_string(), |ratio| ratio.to_string());
+            rendered.extend_from_slice(format!(" {GEAR}{ratio}{RESET}").as_bytes());
+        }
+        rendered.push(b'\n');
+    }
+    String::from_utf8_lossy(&rendered).into_owned()
+}
+
+/// A colour for each cell, for [`Grid::write_svg`] or [`Grid::write_ppm`]:
+/// the same colours as [`render`] uses on a black background, with other
+/// symbols in white.
+pub fn image(schematic: &Schematic, rule: &GearRule) -> Grid<Rgb> {
+    let (styles, _) = styles(schematic, rule);
+    let mut colours = styles.map(|style| match style {
+        Style::Plain => Rgb::BLACK,
+        Style::Part => Rgb(0, 192, 0),
+        Style::Unattached => Rgb(96, 96, 96),
+        Style::Gear => Rgb(255, 192, 0),
+    });
+    for symbol in schematic.symbols() {
+        if styles[symbol.position] == Style::Plain {
+            colours[symbol.position] = Rgb::WHITE;
+        }
+    }
+    colours
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day_03::EXAMPLE;
+
+    #[test]
+    fn styles() {
+        let schematic = Schematic::parse(EXAMPLE).unwrap();
+        let rendered = render(&schematic, &GearRule::new(), None);
+        assert_eq!(
+            rendered.lines().take(4).collect::<Vec<_>>(),
+            [
+                "\x1b[1;32m467\x1b[0m..\x1b[2m114\x1b[0m..",
+                "...\x1b[1;33m*\x1b[0m...... \x1b[1;33m16345\x1b[0m",
+                "..\x1b[1;32m35\x1b[0m..\x1b[1;32m633\x1b[0m.",
+                "......#...",
+            ]
+        );
+    }
+
+    #[yare::parameterized(
+        whole = { 0..4, "é\x1b[1;32m1\x1b[0m*" },
+        cut = { 1..4, "\u{fffd}\x1b[1;32m1\x1b[0m*" },
+    )]
+    fn non_ascii(cols: Range<usize>, expected: &str) {
+        let schematic = Schematic::parse("é1*\n").unwrap();
+        let viewport = Viewport { rows: 0..1, cols };
+        let rendered = render(&schematic, &GearRule::new(), Some(&viewport));
+        assert_eq!(rendered, format!("{expected}\n"));
+    }
+
+    #[test]
+    fn image_colours() {
+        let schematic = Schematic::parse(EXAMPLE).unwrap();
+        let colours = image(&schematic, &GearRule::new());
+        assert_eq!(colours[(0, 0)], Rgb(0, 192, 0));
+        assert_eq!(colours[(3, 0)], Rgb::BLACK);
+        assert_eq!(colours[(5, 0)], Rgb(96, 96, 96));
+        assert_eq!(colours[(3, 1)], Rgb(255, 192, 0));
+        assert_eq!(colours[(6, 3)], Rgb::WHITE);
+    }
+
+    #[yare::parameterized(
+        window = { 1..3, 2..7, &[".\x1b[1;33m*\x1b[0m... \x1b[1;33m16345\x1b[0m", "\x1b[1;32m35\x1b[0m..\x1b[1;32m6\x1b[0m"] },
+        past_the_edges = { 8..20, 8..20, &["..", ".."] },
+        empty = { 2..2, 0..10, &[] },
+    )]
+    fn viewport(rows: Range<usize>, cols: Range<usize>, expected: &[&str]) {
+        let schematic = Schematic::parse(EXAMPLE).unwrap();
+        let viewport = Viewport { rows, cols };
+        let rendered = render(&schematic, &GearRule::new(), Some(&viewport));
+        assert_eq!(rendered.lines().collect::<Vec<_>>(), expected);
+    }
+}
@@ -0,0 +1,423 @@
+//! The numbers and symbols of an engine schematic, and which of them touch.
+
+use std::fmt;
+
+use itertools::Itertools;
+
+use super::{gears::GearRule, is_symbol, scan_in_number};
+use crate::grid::{point::Point, Grid, Neighbourhood, ParseGridError, Policy, Topology};
+
+/// A run of digits within a single row, covering columns `start..end`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Number {
+    pub row: usize,
+    pub start: usize,
+    pub end: usize,
+    pub value: u32,
+}
+
+impl Number {
+    /// Every number in `line`, which is row `row` of a schematic, from left
+    /// to right.
+    pub fn scan_row(row: usize, line: &[u8]) -> Result<Vec<Number>, ParseSchematicError> {
+        let starts = (0..line.len()).filter(|&col| {
+            line[col].is_ascii_digit() && (col == 0 || !line[col - 1].is_ascii_digit())
+        });
+        starts
+            .map(|start| {
+                let end = start
+                    + line[start..]
+                        .iter()
+                        .take_while(|c| c.is_ascii_digit())
+                        .count();
+                let value = scan_in_number(line, start)
+                    .ok_or(ParseSchematicError::TooLarge { row, start })?;
+                Ok(Number {
+                    row,
+                    start,
+                    end,
+                    value,
+                })
+            })
+            .collect()
+    }
+
+    /// The cells covered by the number's digits, from left to right.
+    pub fn cells(&self) -> impl Iterator<Item = Point> {
+        let row = self.row;
+        (self.start..self.end).map(move |col| Point::new(col, row))
+    }
+
+    /// Whether `point` is one of the number's cells or next to one,
+    /// diagonals included.
+    pub fn reaches(&self, point: Point) -> bool {
+        self.row.abs_diff(point.y) <= 1 && self.start <= point.x + 1 && point.x <= self.end
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Symbol {
+    pub position: Point,
+    pub symbol: u8,
+}
+
+/// Every number and symbol, each found once, with the edges of the bipartite
+/// graph joining the ones that touch, diagonals included.
+#[derive(Debug, Clone)]
+pub struct Schematic {
+    grid: Grid<u8>,
+    numbers: Vec<Number>,
+    symbols: Vec<Symbol>,
+    /// The indices of the numbers touching each symbol, in reading order.
+    symbol_edges: Vec<Vec<usize>>,
+    /// The indices of the symbols touching each number, in reading order.
+    number_edges: Vec<Vec<usize>>,
+}
+
+/// How to read a schematic, and which cells around a symbol it reaches. The
+/// default is the puzzle's: short lines are padded with empty space, and
+/// symbols reach the eight cells around them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Options {
+    pub policy: Policy,
+    pub neighbourhood: Neighbourhood,
+    /// Numbers never wrap around a torus, but symbols can reach across its
+    /// edges.
+    pub topology: Topology,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options {
+            policy: Policy::Pad(b'.'),
+            neighbourhood: Neighbourhood::default(),
+            topology: Topology::default(),
+        }
+    }
+}
+
+/// Why text isn't a schematic.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseSchematicError {
+    Grid(ParseGridError),
+    /// The number starting at this row and column doesn't fit in a `u32`.
+    TooLarge {
+        row: usize,
+        start: usize,
+    },
+}
+
+impl fmt::Display for ParseSchematicError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseSchematicError::Grid(error) => error.fmt(f),
+            ParseSchematicError::TooLarge { row, start } => write!(
+                f,
+                "the number at line {}, column {} is too large",
+                row + 1,
+                start + 1
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ParseSchematicError {}
+
+impl From<ParseGridError> for ParseSchematicError {
+    fn from(error: ParseGridError) -> Self {
+        ParseSchematicError::Grid(error)
+    }
+}
+
+impl Schematic {
+    /// The schematic drawn in `input` with the default options, or `None` if
+    /// it isn't one.
+    pub fn parse(input: &str) -> Option<Self> {
+        Self::parse_with(input, &Options::default()).ok()
+    }
+
+    /// Pass [`Policy::Strict`] to reject anything that isn't a clean
+    /// rectangle.
+    pub fn parse_with(input: &str, options: &Options) -> Result<Self, ParseSchematicError> {
+        Self::from_grid(&Grid::parse_with(input, options.policy)?, options)
+    }
+
+    /// Only the neighbourhood and topology of `options` are used, since the
+    /// grid has already been read.
+    pub fn from_grid(grid: &Grid<u8>, options: &Options) -> Result<Self, ParseSchematicError> {
+        let mut numbers = Vec::new();
+        let mut number_at = grid.map(|_| None);
+        for (row, line) in grid.rows().enumerate() {
+            for number in Number::scan_row(row, line)? {
+                for cell in number.cells() {
+                    number_at[cell] = Some(numbers.len());
+                }
+                numbers.push(number);
+            }
+        }
+
+        let symbols = grid
+            .cells()
+            .filter(|(_, &c)| is_symbol(c))
+            .map(|(position, &symbol)| Symbol { position, symbol })
+            .collect_vec();
+        let symbol_edges = symbols
+            .iter()
+            .map(|symbol| {
+                grid.neighbours(symbol.position, &options.neighbourhood, options.topology)
+                    .filter_map(|point| *number_at.at(point)?)
+                    .sorted_unstable()
+                    .dedup()
+                    .collect_vec()
+            })
+            .collect_vec();
+        let mut number_edges = vec![Vec::new(); numbers.len()];
+        for (symbol, touching) in symbol_edges.iter().enumerate() {
+            for &number in touching {
+                number_edges[number].push(symbol);
+            }
+        }
+
+        Ok(Schematic {
+            grid: grid.clone(),
+            numbers,
+            symbols,
+            symbol_edges,
+            number_edges,
+        })
+    }
+
+    /// The schematic as drawn.
+    pub fn grid(&self) -> &Grid<u8> {
+        &self.grid
+    }
+
+    /// Every number, in reading order.
+    pub fn numbers(&self) -> &[Number] {
+        &self.numbers
+    }
+
+    /// Every symbol, in reading order.
+    pub fn symbols(&self) -> &[Symbol] {
+        &self.symbols
+    }
+
+    /// The numbers touching the `symbol`th symbol.
+    pub fn numbers_touching(&self, symbol: usize) -> impl Iterator<Item = &Number> {
+        self.symbol_edges[symbol]
+            .iter()
+            .map(|&number| &self.numbers[number])
+    }
+
+    /// The symbols touching the `number`th number.
+    pub fn symbols_touching(&self, number: usize) -> impl Iterator<Item = &Symbol> {
+        self.number_edges[number]
+            .iter()
+            .map(|&symbol| &self.symbols[symbol])
+    }
+
+    /// The numbers touching at least one symbol, each once, in reading order.
+    pub fn part_numbers(&self) -> impl Iterator<Item = &Number> {
+        self.numbers
+            .iter()
+            .zip(&self.number_edges)
+            .filter(|(_, symbols)| !symbols.is_empty())
+            .map(|(number, _)| number)
+    }
+
+    /// Each symbol that `rule` says is a gear, with the numbers it touches.
+    pub fn gears<'a>(
+        &'a self,
+        rule: &'a GearRule,
+    ) -> impl Iterator<Item = (&'a Symbol, Vec<&'a Number>)> {
+        self.symbols
+            .iter()
+            .zip(&self.symbol_edges)
+            .filter(|(symbol, numbers)| rule.is_gear(symbol.symbol, numbers.len()))
+            .map(|(symbol, numbers)| {
+                let numbers = numbers.iter().map(|&number| &self.numbers[number]);
+                (symbol, numbers.collect_vec())
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid::point::Direction;
+    use indoc::indoc;
+
+    #[test]
+    fn spans_and_edges() {
+        let schematic = Schematic::parse(indoc! {
+            "
+            1.1*
+            ..#.
+            11..
+            "
+        })
+        .unwrap();
+        assert_eq!(
+            schematic.numbers(),
+            [
+                Number {
+                    row: 0,
+                    start: 0,
+                    end: 1,
+                    value: 1
+                },
+                Number {
+                    row: 0,
+                    start: 2,
+                    end: 3,
+                    value: 1
+                },
+                Number {
+                    row: 2,
+                    start: 0,
+                    end: 2,
+                    value: 11
+                },
+            ]
+        );
+        assert_eq!(
+            schematic.symbols(),
+            [
+                Symbol {
+                    position: Point::new(3, 0),
+                    symbol: b'*'
+                },
+                Symbol {
+                    position: Point::new(2, 1),
+                    symbol: b'#'
+                },
+            ]
+        );
+        assert_eq!(
+            schematic.numbers_touching(0).collect_vec(),
+            [&schematic.numbers()[1]]
+        );
+        assert_eq!(
+            schematic
+                .numbers_touching(1)
+                .map(|number| number.start)
+                .collect_vec(),
+            [2, 0]
+        );
+        assert_eq!(schematic.symbols_touching(0).count(), 0);
+        assert_eq!(schematic.symbols_touching(1).count(), 2);
+        assert_eq!(
+            schematic
+                .part_numbers()
+                .map(|number| number.value)
+                .collect_vec(),
+            [1, 11]
+        );
+        assert_eq!(schematic.gears(&GearRule::new()).count(), 0);
+    }
+
+    #[yare::parameterized(
+        own_cell = { (3, 1), true },
+        above_left = { (1, 0), true },
+        below_right = { (4, 2), true },
+        right = { (5, 1), false },
+        left = { (0, 1), false },
+        two_rows_below = { (2, 3), false },
+    )]
+    fn reaches(point: (usize, usize), expected: bool) {
+        let number = Number {
+            row: 1,
+            start: 2,
+            end: 4,
+            value: 12,
+        };
+        assert_eq!(number.reaches(point.into()), expected);
+        let by_cells = number.cells().any(|cell| cell.chebyshev(point.into()) <= 1);
+        assert_eq!(by_cells, expected);
+    }
+
+    fn part_numbers(input: &str, options: &Options) -> Vec<u32> {
+        Schematic::parse_with(input, options)
+            .unwrap()
+            .part_numbers()
+            .map(|number| number.value)
+            .collect_vec()
+    }
+
+    #[yare::parameterized(
+        strict = { Policy::Strict, Err(ParseGridError::Ragged { line: 2, len: 2, expected: 3 }.into()) },
+        padded = { Policy::Pad(b'.'), Ok(vec![12]) },
+    )]
+    fn ragged(policy: Policy, expected: Result<Vec<u32>, ParseSchematicError>) {
+        let options = Options {
+            policy,
+            ..Default::default()
+        };
+        let schematic = Schematic::parse_with("12*\n..\n", &options);
+        let part_numbers = schematic.map(|schematic| {
+            schematic
+                .part_numbers()
+                .map(|number| number.value)
+                .collect_vec()
+        });
+        assert_eq!(part_numbers, expected);
+    }
+
+    #[test]
+    fn too_large() {
+        let error = Schematic::parse_with("...\n.4294967296*", &Options::default()).unwrap_err();
+        assert_eq!(error, ParseSchematicError::TooLarge { row: 1, start: 1 });
+        assert_eq!(
+            error.to_string(),
+            "the number at line 2, column 2 is too large"
+        );
+    }
+
+    #[yare::parameterized(
+        moore = { Neighbourhood::Moore, &[1, 2, 3] },
+        von_neumann = { Neighbourhood::VonNeumann, &[2, 3] },
+        just_below = { Neighbourhood::Custom(vec![Direction::S.vector()]), &[3] },
+    )]
+    fn neighbourhoods(neighbourhood: Neighbourhood, expected: &[u32]) {
+        let input = indoc! {
+            "
+            1...
+            .#2.
+            .3..
+            "
+        };
+        let options = Options {
+            neighbourhood,
+            ..Default::default()
+        };
+        assert_eq!(part_numbers(input, &options), expected);
+    }
+
+    #[yare::parameterized(
+        bounded = { Topology::Bounded, &[] },
+        toroidal = { Topology::Toroidal, &[1, 2] },
+    )]
+    fn topologies(topology: Topology, expected: &[u32]) {
+        let input = indoc! {
+            "
+            1...2
+            .....
+            ....*
+            "
+        };
+        let options = Options {
+            topology,
+            ..Default::default()
+        };
+        assert_eq!(part_numbers(input, &options), expected);
+    }
+
+    #[test]
+    fn same_value_twice() {
+        let schematic = Schematic::parse("1*1").unwrap();
+        let rule = GearRule::new();
+        let gears = schematic.gears(&rule).collect_vec();
+        assert_eq!(gears.len(), 1);
+        assert_ne!(gears[0].1[0], gears[0].1[1]);
+    }
+}
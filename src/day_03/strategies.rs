@@ -0,0 +1,44 @@
+//! Proptest strategies that build schematics cell by cell, so a failing case
+//! shrinks to the smallest grid and fewest numbers and symbols that still
+//! fail.
+
+use std::ops::Range;
+
+use proptest::{collection::vec, prelude::*};
+
+const SYMBOLS: &[u8] = b"*#+$/@=%&-";
+
+/// A symbol, or empty space, which is more common.
+fn cell() -> impl Strategy<Value = u8> {
+    prop_oneof![
+        3 => Just(b'.'),
+        1 => prop::sample::select(SYMBOLS),
+    ]
+}
+
+/// Empty space, a symbol, or a number followed by a cell that isn't a digit
+/// so that it can't run on into the next one.
+fn token() -> impl Strategy<Value = Vec<u8>> {
+    prop_oneof![
+        cell().prop_map(|cell| vec![cell]),
+        (1..1000u32, cell()).prop_map(|(number, after)| {
+            let mut token = number.to_string().into_bytes();
+            token.push(after);
+            token
+        }),
+    ]
+}
+
+/// A rectangular schematic with a size in the given ranges, each row ending
+/// in a newline. Rows are cut or padded with `.` to fit the width.
+pub fn schematic(widths: Range<usize>, heights: Range<usize>) -> impl Strategy<Value = String> {
+    (widths, vec(vec(token(), 0..20), heights)).prop_map(|(width, rows)| {
+        rows.into_iter()
+            .map(|tokens| {
+                let mut row = tokens.concat();
+                row.resize(width, b'.');
+                String::from_utf8(row).unwrap() + "\n"
+            })
+            .collect()
+    })
+}
@@ -0,0 +1,194 @@
+//! Both parts read straight from a reader, holding only the three rows around
+//! the one being scanned, so tall schematics take constant memory.
+
+use std::io::{self, BufRead};
+
+use super::{
+    gears::GearRule,
+    is_symbol,
+    schematic::{Number, ParseSchematicError},
+};
+use crate::grid::point::Point;
+
+/// A row of the schematic with the numbers found in it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Row {
+    index: usize,
+    cells: Vec<u8>,
+    numbers: Vec<Number>,
+}
+
+impl Row {
+    /// Row `row` of a schematic, drawn in `cells`.
+    pub fn new(row: usize, cells: &[u8]) -> Result<Self, ParseSchematicError> {
+        Ok(Row {
+            index: row,
+            cells: cells.to_vec(),
+            numbers: Number::scan_row(row, cells)?,
+        })
+    }
+
+    /// Whether any cell in the columns around `start..end` is a symbol. The
+    /// row may be shorter than the others, as if padded with empty space.
+    fn has_symbol_around(&self, start: usize, end: usize) -> bool {
+        let end = (end + 1).min(self.cells.len());
+        let start = start.saturating_sub(1).min(end);
+        self.cells[start..end].iter().any(|&c| is_symbol(c))
+    }
+
+    /// The numbers in this row reaching `point`.
+    fn numbers_around(&self, point: Point) -> impl Iterator<Item = &Number> {
+        self.numbers
+            .iter()
+            .filter(move |number| number.reaches(point))
+    }
+}
+
+fn invalid(error: impl Into<ParseSchematicError>) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, error.into())
+}
+
+fn too_large(what: &str) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("the {what} is too large"),
+    )
+}
+
+/// The part number total and gear ratio total of the row in the middle of
+/// `window`, with the rows above and below it if there are any. Every number
+/// and gear is counted by the row it's on, so adding up the scans of each row
+/// counts each of them once.
+pub fn scan(window: [Option<&Row>; 3], rule: &GearRule) -> Option<(u32, u32)> {
+    let [_, Some(row), _] = window else {
+        return Some((0, 0));
+    };
+    let rows = || window.into_iter().flatten();
+
+    let mut parts = 0u32;
+    for number in &row.numbers {
+        if rows().any(|row| row.has_symbol_around(number.start, number.end)) {
+            parts = parts.checked_add(number.value)?;
+        }
+    }
+    let mut ratios = 0u32;
+    for (col, &symbol) in row.cells.iter().enumerate() {
+        if !is_symbol(symbol) {
+            continue;
+        }
+        let point = Point::new(col, row.index);
+        let numbers: Vec<_> = rows().flat_map(|row| row.numbers_around(point)).collect();
+        if rule.is_gear(symbol, numbers.len()) {
+            ratios = ratios.checked_add(rule.ratio(&numbers)?)?;
+        }
+    }
+    Some((parts, ratios))
+}
+
+/// Both parts for the schematic in `reader`, with gears found by `rule`,
+/// reading it the way [`super::part_1`] and [`super::part_2`] do. Numbers and
+/// answers that don't fit in a `u32` are [`io::ErrorKind::InvalidData`]
+/// errors.
+pub fn solve_reader(mut reader: impl BufRead, rule: &GearRule) -> io::Result<(u32, u32)> {
+    let mut line = Vec::new();
+    let mut window: [Option<Row>; 3] = [None, None, None];
+    let (mut parts, mut ratios) = (0u32, 0u32);
+    let mut add = |window: &[Option<Row>; 3]| {
+        let (row_parts, row_ratios) = scan(
+            [&window[0], &window[1], &window[2]].map(Option::as_ref),
+            rule,
+        )
+        .ok_or_else(|| too_large("total"))?;
+        parts = parts
+            .checked_add(row_parts)
+            .ok_or_else(|| too_large("part number total"))?;
+        ratios = ratios
+            .checked_add(row_ratios)
+            .ok_or_else(|| too_large("gear ratio total"))?;
+        io::Result::Ok(())
+    };
+
+    for line_number in 1.. {
+        line.clear();
+        if reader.read_until(b'\n', &mut line)? == 0 {
+            break;
+        }
+        let cells = line.strip_suffix(b"\n").unwrap_or(&line);
+        // short and blank rows are left as they are, since padding them with
+        // empty space wouldn't change what anything touches
+        let cells = cells.strip_suffix(b"\r").unwrap_or(cells);
+        window.rotate_left(1);
+        window[2] = Some(Row::new(line_number - 1, cells).map_err(invalid)?);
+        add(&window)?;
+    }
+    window.rotate_left(1);
+    window[2] = None;
+    add(&window)?;
+    Ok((parts, ratios))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day_03::{gears, part_1, part_2, schematic::Schematic, strategies, EXAMPLE};
+    use proptest::prelude::*;
+
+    fn both_parts(input: &str) -> Option<(u32, u32)> {
+        part_1(input).zip(part_2(input))
+    }
+
+    #[yare::parameterized(
+        example = { EXAMPLE },
+        one_row = { "1*1" },
+        one_column = { "2\n*\n3\n" },
+        shared = { "12*34\n..*..\n" },
+        empty = { "" },
+        ragged = { "12*\n..\n" },
+        short_above = { "..\n....12\n*\n" },
+        gap = { "1*.\n\n.*2\n" },
+        trailing_blank = { "1*.\n\n" },
+        crlf = { "1*.\r\n...\r\n" },
+        too_large = { "...\n4294967296*\n" },
+        ratio_too_large = { "65536*65536" },
+    )]
+    fn matches_parts(input: &str) {
+        let streamed = solve_reader(input.as_bytes(), &GearRule::new()).ok();
+        assert_eq!(streamed, both_parts(input));
+    }
+
+    #[test]
+    fn crlf() {
+        let input = EXAMPLE.replace('\n', "\r\n");
+        let totals = solve_reader(input.as_bytes(), &GearRule::new()).unwrap();
+        assert_eq!(totals, (4361, 467835));
+    }
+
+    #[test]
+    fn errors() {
+        let error = solve_reader("...\n4294967296*\n".as_bytes(), &GearRule::new()).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+        assert_eq!(
+            error.to_string(),
+            "the number at line 2, column 1 is too large"
+        );
+    }
+
+    #[test]
+    fn other_rules() {
+        let rule = GearRule::new()
+            .any_symbol()
+            .count(gears::Count::AtLeast(1))
+            .aggregate(gears::Aggregate::Sum);
+        let schematic = Schematic::parse(EXAMPLE).unwrap();
+        let (_, ratios) = solve_reader(EXAMPLE.as_bytes(), &rule).unwrap();
+        assert_eq!(Some(ratios), rule.total(&schematic));
+    }
+
+    proptest! {
+        #[test]
+        fn matches_parts_on_any_schematic(input in strategies::schematic(1..40, 1..40)) {
+            let streamed = solve_reader(input.as_bytes(), &GearRule::new()).ok();
+            prop_assert_eq!(streamed, both_parts(&input));
+        }
+    }
+}
@@ -0,0 +1,93 @@
+//! Random scratchcards for stress testing.
+
+use itertools::Itertools;
+
+use crate::rng::Rng;
+
+#[derive(Debug, Clone)]
+pub struct Config {
+    pub cards: usize,
+    /// How many winning numbers are on each card.
+    pub winners: usize,
+    /// How many numbers were picked on each card.
+    pub picks: usize,
+    /// The largest number that can appear on a card.
+    pub max_number: u32,
+    /// How likely each pick is to be one of the winning numbers.
+    pub win_rate: f64,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            cards: 200,
+            winners: 10,
+            picks: 25,
+            max_number: 99,
+            win_rate: 0.15,
+        }
+    }
+}
+
+fn numbers(numbers: &[u32]) -> String {
+    numbers.iter().map(|n| format!("{n:2}")).join(" ")
+}
+
+/// A card whose number of matches never runs past the last card, as the
+/// puzzle guarantees.
+fn card(rng: &mut Rng, config: &Config, id: usize) -> String {
+    assert!(
+        config.winners + config.picks <= config.max_number as usize,
+        "not enough distinct numbers for a card"
+    );
+    let mut pool = (1..=config.max_number).collect_vec();
+    rng.shuffle(&mut pool);
+    let (winners, others) = pool.split_at(config.winners);
+
+    let wins = (0..config.picks)
+        .filter(|_| rng.chance(config.win_rate))
+        .count()
+        .min(config.winners)
+        .min(config.cards - id);
+    let mut picks = winners[..wins]
+        .iter()
+        .chain(&others[..config.picks - wins])
+        .copied()
+        .collect_vec();
+    rng.shuffle(&mut picks);
+
+    let width = config.cards.to_string().len();
+    format!(
+        "Card {id:width$}: {} | {}\n",
+        numbers(winners),
+        numbers(&picks)
+    )
+}
+
+pub fn generate(rng: &mut Rng, config: &Config) -> String {
+    (1..=config.cards).map(|id| card(rng, config, id)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day_04::input_generator;
+
+    #[yare::parameterized(
+        never = { 0.0 },
+        sometimes = { 0.15 },
+        always = { 1.0 },
+    )]
+    fn cards_parse(win_rate: f64) {
+        let config = Config {
+            win_rate,
+            ..Config::default()
+        };
+        let input = input_generator(&generate(&mut Rng::new(1), &config));
+        assert_eq!(input.cards.len(), config.cards);
+        assert!(input
+            .cards
+            .iter()
+            .all(|card| card.id + card.winner_count <= config.cards));
+    }
+}
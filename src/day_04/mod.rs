@@ -1,4 +1,7 @@
-use std::ops::Shl;
+pub mod generate;
+pub mod reference;
+#[cfg(test)]
+mod strategies;
 
 use nom::{
     bytes::complete::{tag, tag_no_case},
@@ -10,7 +13,7 @@ use nom::{
 };
 
 #[derive(Debug)]
-struct Card {
+pub struct Card {
     id: usize,
     winner_count: usize,
 }
@@ -32,7 +35,7 @@ fn parse_numbers(input: &str) -> IResult<&str, Vec<u32>> {
     separated_list1(space1, map_res(digit1, str::parse::<u32>))(input)
 }
 
-fn parse_card(input: &str) -> Option<Card> {
+pub fn parse_card(input: &str) -> Option<Card> {
     let (_, (id, _, _, _, winners, _, _, _, picks)) = tuple((
         parse_id,
         space0,
@@ -57,36 +60,52 @@ pub fn input_generator(input: &str) -> Input {
 }
 
 #[aoc(day4, part1)]
-pub fn part_1(input: &Input) -> u32 {
+pub fn part_1(input: &Input) -> Option<u32> {
     input
         .cards
         .iter()
         .filter(|card| card.winner_count > 0)
-        .fold(0, |acc, card| acc + 1usize.shl(card.winner_count - 1)) as u32
+        .try_fold(0u32, |acc, card| {
+            acc.checked_add(1u32.checked_shl(card.winner_count as u32 - 1)?)
+        })
 }
 
+/// `None` if a card wins copies of cards past the end of the table.
 #[aoc(day4, part2)]
-pub fn part_2(input: &Input) -> u32 {
+pub fn part_2(input: &Input) -> Option<u32> {
     input
         .cards
         .iter()
-        .fold(vec![1; input.cards.len()], |mut acc, card| {
-            (card.id..card.id + card.winner_count)
-                .for_each(|winner_id| acc[winner_id] += acc[card.id - 1]);
-            acc
-        })
+        .try_fold(vec![1u32; input.cards.len()], |mut acc, card| {
+            let copies = *acc.get(card.id.checked_sub(1)?)?;
+            for winner_id in card.id..card.id.checked_add(card.winner_count)? {
+                let winner = acc.get_mut(winner_id)?;
+                *winner = winner.checked_add(copies)?;
+            }
+            Some(acc)
+        })?
+        .iter()
+        .try_fold(0u32, |total, &copies| total.checked_add(copies))
+}
+
+/// How many winning numbers were picked on each card.
+pub fn explain(input: &Input) -> String {
+    input
+        .cards
         .iter()
-        .sum()
+        .map(|Card { id, winner_count }| format!("Card {id}: {winner_count} winners\n"))
+        .collect()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use indoc::indoc;
+    use proptest::prelude::*;
 
     #[test]
     fn test() {
-        let input = input_generator(indoc! {
+        let raw_input = indoc! {
             "
             Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
             Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
@@ -95,8 +114,38 @@ mod tests {
             Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
             Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11
             "
-        });
-        assert_eq!(part_1(&input), 13);
-        assert_eq!(part_2(&input), 30);
+        };
+        let input = input_generator(raw_input);
+        assert_eq!(part_1(&input), Some(13));
+        assert_eq!(part_2(&input), Some(30));
+        assert_eq!(reference::part_1(raw_input), 13);
+        assert_eq!(reference::part_2(raw_input), 30);
+        insta::assert_snapshot!("explain", explain(&input));
+    }
+
+    #[yare::parameterized(
+        wins_past_end = { "Card 1: 1 2 | 1 2", Some(2), None },
+        card_zero = { "Card 0: 1 | 2", Some(0), None },
+        missing_card = { "Card 2: 1 | 2", Some(0), None },
+        too_many_wins = {
+            &format!("Card 1: {0} | {0}", (1..=33).map(|n| n.to_string()).collect::<Vec<_>>().join(" ")),
+            None,
+            None,
+        },
+    )]
+    fn invalid_cards(input: &str, expected_part_1: Option<u32>, expected_part_2: Option<u32>) {
+        let input = input_generator(input);
+        assert_eq!(part_1(&input), expected_part_1);
+        assert_eq!(part_2(&input), expected_part_2);
+    }
+
+    proptest! {
+        // copies grow exponentially with the win rate, so few cards keep the
+        // brute-force reference quick
+        #[test]
+        fn matches_reference(input in strategies::pile(1..16)) {
+            prop_assert_eq!(part_1(&input_generator(&input)), Some(reference::part_1(&input)));
+            prop_assert_eq!(part_2(&input_generator(&input)), Some(reference::part_2(&input)));
+        }
     }
 }
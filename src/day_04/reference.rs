@@ -0,0 +1,41 @@
+//! Slow but straightforward solvers used to cross-check the optimized ones.
+
+/// The number of picks on each card that appear among its winning numbers.
+fn winner_counts(input: &str) -> Vec<usize> {
+    input
+        .lines()
+        .filter_map(|line| {
+            let (_, numbers) = line.split_once(':')?;
+            let (winners, picks) = numbers.split_once('|')?;
+            let winners: Vec<&str> = winners.split_whitespace().collect();
+            Some(
+                picks
+                    .split_whitespace()
+                    .filter(|pick| winners.contains(pick))
+                    .count(),
+            )
+        })
+        .collect()
+}
+
+pub fn part_1(input: &str) -> u32 {
+    winner_counts(input)
+        .iter()
+        .map(|&count| match count {
+            0 => 0,
+            _ => 2u32.pow(count as u32 - 1),
+        })
+        .sum()
+}
+
+/// Processes every card copy one at a time until no new copies are won.
+pub fn part_2(input: &str) -> u32 {
+    let counts = winner_counts(input);
+    let mut pending: Vec<usize> = (0..counts.len()).collect();
+    let mut processed = 0;
+    while let Some(card) = pending.pop() {
+        processed += 1;
+        pending.extend((card + 1..=card + counts[card]).filter(|&copy| copy < counts.len()));
+    }
+    processed
+}
@@ -0,0 +1,52 @@
+//! Proptest strategies that build scratchcards number by number, so a failing
+//! case shrinks to the fewest cards and numbers that still fail.
+
+use std::{collections::BTreeSet, ops::Range};
+
+use itertools::Itertools;
+use proptest::{
+    collection::{btree_set, vec},
+    prelude::*,
+};
+
+/// The winning numbers on a card, picks from the same range that often match
+/// them, and picks from above it that never do.
+type Card = (BTreeSet<u32>, BTreeSet<u32>, BTreeSet<u32>);
+
+fn card() -> impl Strategy<Value = Card> {
+    (
+        btree_set(1..30u32, 1..8),
+        btree_set(1..30u32, 0..8),
+        btree_set(30..100u32, 1..8),
+    )
+}
+
+/// A pile of `cards` cards numbered from 1. Picks that would win copies of
+/// cards past the end of the pile are dropped, as the puzzle guarantees.
+pub fn pile(cards: Range<usize>) -> impl Strategy<Value = String> {
+    vec(card(), cards).prop_map(|cards| {
+        let count = cards.len();
+        let mut pile = String::new();
+        for (index, (winners, guesses, misses)) in cards.into_iter().enumerate() {
+            let mut picks = Vec::new();
+            let mut wins = 0;
+            for guess in guesses {
+                if winners.contains(&guess) {
+                    if index + wins + 1 >= count {
+                        continue;
+                    }
+                    wins += 1;
+                }
+                picks.push(guess);
+            }
+            picks.extend(misses);
+            pile += &format!(
+                "Card {}: {} | {}\n",
+                index + 1,
+                winners.iter().join(" "),
+                picks.iter().join(" ")
+            );
+        }
+        pile
+    })
+}
@@ -1,3 +1,5 @@
+pub mod reference;
+
 pub struct Input {
 
 }
@@ -8,12 +10,12 @@ pub fn input_generator(input: &str) -> Input {
 }
 
 #[aoc(dayxx, part1)]
-pub fn part_1(input: &Input) -> u32 {
+pub fn part_1(input: &Input) -> Option<u32> {
 
 }
 
 #[aoc(dayxx, part2)]
-pub fn part_2(input: &Input) -> u32 {
+pub fn part_2(input: &Input) -> Option<u32> {
 
 }
 
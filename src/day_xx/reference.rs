@@ -0,0 +1,9 @@
+//! Slow but straightforward solvers used to cross-check the optimized ones.
+
+pub fn part_1(input: &str) -> u32 {
+
+}
+
+pub fn part_2(input: &str) -> u32 {
+
+}
@@ -0,0 +1,181 @@
+//! Grids drawn as images, with a colour picked for each cell.
+
+use std::{fmt, io};
+
+use super::Grid;
+
+/// The longest line the plain PPM format allows.
+const PPM_LINE_LIMIT: usize = 70;
+
+/// A colour given by its red, green and blue components.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rgb(pub u8, pub u8, pub u8);
+
+impl Rgb {
+    pub const BLACK: Rgb = Rgb(0, 0, 0);
+    pub const WHITE: Rgb = Rgb(255, 255, 255);
+}
+
+impl fmt::Display for Rgb {
+    /// The colour as a hex triplet, such as `#ff8000`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "#{:02x}{:02x}{:02x}", self.0, self.1, self.2)
+    }
+}
+
+impl<T> Grid<T> {
+    /// A plain (ASCII) PPM image, with each cell drawn as a square of
+    /// `scale` by `scale` pixels. Each row of pixels starts a new line, and
+    /// is wrapped between pixels to keep lines within the format's limit.
+    pub fn write_ppm(
+        &self,
+        mut out: impl io::Write,
+        scale: usize,
+        colour: impl Fn(&T) -> Rgb,
+    ) -> io::Result<()> {
+        writeln!(out, "P3")?;
+        writeln!(out, "{} {}", self.width * scale, self.height * scale)?;
+        writeln!(out, "255")?;
+        for row in self.rows() {
+            let pixels = row
+                .iter()
+                .map(|cell| {
+                    let Rgb(r, g, b) = colour(cell);
+                    format!("{r} {g} {b}")
+                })
+                .flat_map(|pixel| std::iter::repeat_n(pixel, scale));
+            let mut lines = vec![String::new()];
+            for pixel in pixels {
+                let line = lines.last_mut().unwrap();
+                if line.is_empty() {
+                    *line = pixel;
+                } else if line.len() + 1 + pixel.len() <= PPM_LINE_LIMIT {
+                    line.push(' ');
+                    line.push_str(&pixel);
+                } else {
+                    lines.push(pixel);
+                }
+            }
+            for _ in 0..scale {
+                for line in &lines {
+                    writeln!(out, "{line}")?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// An SVG image, with each cell drawn as a square `scale` pixels wide.
+    /// Runs of cells in a row with the same colour share a rectangle.
+    pub fn write_svg(
+        &self,
+        mut out: impl io::Write,
+        scale: usize,
+        colour: impl Fn(&T) -> Rgb,
+    ) -> io::Result<()> {
+        writeln!(
+            out,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{}" height="{}" viewBox="0 0 {} {}" shape-rendering="crispEdges">"#,
+            self.width * scale,
+            self.height * scale,
+            self.width,
+            self.height
+        )?;
+        for (y, row) in self.rows().enumerate() {
+            let mut x = 0;
+            while x < row.len() {
+                let fill = colour(&row[x]);
+                let run = row[x..]
+                    .iter()
+                    .take_while(|cell| colour(cell) == fill)
+                    .count();
+                writeln!(
+                    out,
+                    r#"<rect x="{x}" y="{y}" width="{run}" height="1" fill="{fill}"/>"#
+                )?;
+                x += run;
+            }
+        }
+        writeln!(out, "</svg>")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indoc::indoc;
+
+    fn colour(&cell: &u8) -> Rgb {
+        match cell {
+            b'#' => Rgb::BLACK,
+            _ => Rgb::WHITE,
+        }
+    }
+
+    #[test]
+    fn ppm() {
+        let grid = Grid::parse("#.\n..").unwrap();
+        let mut out = Vec::new();
+        grid.write_ppm(&mut out, 2, colour).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            indoc! {
+                "
+                P3
+                4 4
+                255
+                0 0 0 0 0 0 255 255 255 255 255 255
+                0 0 0 0 0 0 255 255 255 255 255 255
+                255 255 255 255 255 255 255 255 255 255 255 255
+                255 255 255 255 255 255 255 255 255 255 255 255
+                "
+            }
+        );
+    }
+
+    #[test]
+    fn wide_ppm() {
+        let grid = Grid::parse("#.......").unwrap();
+        let mut out = Vec::new();
+        grid.write_ppm(&mut out, 1, colour).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.lines().all(|line| line.len() <= PPM_LINE_LIMIT));
+        assert_eq!(
+            out,
+            indoc! {
+                "
+                P3
+                8 1
+                255
+                0 0 0 255 255 255 255 255 255 255 255 255 255 255 255 255 255 255
+                255 255 255 255 255 255
+                "
+            }
+        );
+    }
+
+    #[test]
+    fn svg() {
+        let grid = Grid::parse("#..\n.##").unwrap();
+        let mut out = Vec::new();
+        grid.write_svg(&mut out, 10, colour).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            indoc! {
+                r##"
+                <svg xmlns="http://www.w3.org/2000/svg" width="30" height="20" viewBox="0 0 3 2" shape-rendering="crispEdges">
+                <rect x="0" y="0" width="1" height="1" fill="#000000"/>
+                <rect x="1" y="0" width="2" height="1" fill="#ffffff"/>
+                <rect x="0" y="1" width="1" height="1" fill="#ffffff"/>
+                <rect x="1" y="1" width="2" height="1" fill="#000000"/>
+                </svg>
+                "##
+            }
+        );
+    }
+
+    #[test]
+    fn hex_triplet() {
+        assert_eq!(Rgb(255, 128, 0).to_string(), "#ff8000");
+    }
+}
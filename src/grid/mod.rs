@@ -0,0 +1,582 @@
+//! A rectangular grid of cells, for the puzzles drawn as a map.
+
+pub mod image;
+pub mod point;
+
+use std::{
+    fmt,
+    ops::{Index, IndexMut},
+};
+
+use point::{Direction, Point, Vector};
+
+/// Cells stored row by row, addressed by `(x, y)` with `(0, 0)` at the top
+/// left.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    width: usize,
+    height: usize,
+}
+
+/// The offsets to the four cells sharing an edge, clockwise from north.
+const ORTHOGONAL: [Vector; 4] = [
+    Direction::N.vector(),
+    Direction::E.vector(),
+    Direction::S.vector(),
+    Direction::W.vector(),
+];
+
+/// The offsets to the eight cells sharing an edge or a corner, clockwise from
+/// north.
+const SURROUNDING: [Vector; 8] = [
+    Direction::N.vector(),
+    Direction::NE.vector(),
+    Direction::E.vector(),
+    Direction::SE.vector(),
+    Direction::S.vector(),
+    Direction::SW.vector(),
+    Direction::W.vector(),
+    Direction::NW.vector(),
+];
+
+/// Which cells around a cell count as its neighbours.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum Neighbourhood {
+    /// The four cells sharing an edge.
+    VonNeumann,
+    /// The eight cells sharing an edge or a corner.
+    #[default]
+    Moore,
+    /// The cells at each offset, in order.
+    Custom(Vec<Vector>),
+}
+
+impl Neighbourhood {
+    /// The offsets to the neighbours, clockwise from north for the built in
+    /// neighbourhoods.
+    pub fn offsets(&self) -> &[Vector] {
+        match self {
+            Neighbourhood::VonNeumann => &ORTHOGONAL,
+            Neighbourhood::Moore => &SURROUNDING,
+            Neighbourhood::Custom(offsets) => offsets,
+        }
+    }
+}
+
+/// What lies past the edges of a grid.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Topology {
+    /// Nothing, so cells on the edges have fewer neighbours.
+    #[default]
+    Bounded,
+    /// The opposite edge, as if the grid were wrapped around a torus.
+    Toroidal,
+}
+
+/// How [`Grid::parse_with`] treats text that isn't a clean rectangle. Only
+/// [`Policy::Exact`] minds lines ending in `\r\n` rather than `\n`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Policy {
+    /// Reject blank lines at the end and lines of different lengths.
+    #[default]
+    Strict,
+    /// Like [`Policy::Strict`], but reject carriage returns too.
+    Exact,
+    /// Drop blank lines at the end, and pad short lines with the given byte to
+    /// the length of the longest.
+    Pad(u8),
+}
+
+/// Why text isn't a grid under [`Policy::Strict`] or [`Policy::Exact`].
+/// Lines are numbered from 1.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseGridError {
+    Ragged {
+        line: usize,
+        len: usize,
+        expected: usize,
+    },
+    CarriageReturn {
+        line: usize,
+    },
+    TrailingBlankLine {
+        line: usize,
+    },
+}
+
+impl fmt::Display for ParseGridError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseGridError::Ragged {
+                line,
+                len,
+                expected,
+            } => write!(
+                f,
+                "line {line} is {len} bytes long, but the first line is {expected}"
+            ),
+            ParseGridError::CarriageReturn { line } => {
+                write!(f, "line {line} ends with a carriage return")
+            }
+            ParseGridError::TrailingBlankLine { line } => {
+                write!(f, "line {line} is a blank line at the end")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ParseGridError {}
+
+impl Grid<u8> {
+    /// One row per line, one cell per byte, under [`Policy::Strict`].
+    pub fn parse(input: &str) -> Option<Self> {
+        Self::parse_with(input, Policy::Strict).ok()
+    }
+
+    pub fn parse_with(input: &str, policy: Policy) -> Result<Self, ParseGridError> {
+        let mut lines: Vec<&str> = input.split_terminator('\n').collect();
+        for (index, line) in lines.iter_mut().enumerate() {
+            if let Some(stripped) = line.strip_suffix('\r') {
+                if policy == Policy::Exact {
+                    return Err(ParseGridError::CarriageReturn { line: index + 1 });
+                }
+                *line = stripped;
+            }
+        }
+        while lines.last().is_some_and(|line| line.is_empty()) {
+            match policy {
+                Policy::Strict | Policy::Exact => {
+                    return Err(ParseGridError::TrailingBlankLine { line: lines.len() })
+                }
+                Policy::Pad(_) => lines.pop(),
+            };
+        }
+
+        let width = lines.iter().map(|line| line.len()).max().unwrap_or(0);
+        let fill = match policy {
+            Policy::Strict | Policy::Exact => {
+                let expected = lines.first().map_or(0, |line| line.len());
+                if let Some((index, line)) = lines
+                    .iter()
+                    .enumerate()
+                    .find(|(_, line)| line.len() != expected)
+                {
+                    return Err(ParseGridError::Ragged {
+                        line: index + 1,
+                        len: line.len(),
+                        expected,
+                    });
+                }
+                b'.'
+            }
+            Policy::Pad(fill) => fill,
+        };
+        let mut cells = Vec::with_capacity(width * lines.len());
+        for line in &lines {
+            cells.extend(line.bytes());
+            cells.resize(cells.len() + width - line.len(), fill);
+        }
+        Ok(Grid {
+            cells,
+            width,
+            height: lines.len(),
+        })
+    }
+}
+
+impl<T> Grid<T> {
+    /// A grid of `width` columns from cells in row-major order, or `None` if
+    /// they don't fill a whole number of rows.
+    pub fn new(width: usize, cells: Vec<T>) -> Option<Self> {
+        let height = match width {
+            0 => cells.is_empty().then_some(0)?,
+            _ => cells
+                .len()
+                .is_multiple_of(width)
+                .then_some(cells.len() / width)?,
+        };
+        Some(Grid {
+            cells,
+            width,
+            height,
+        })
+    }
+
+    /// A grid with the given rows, or `None` if they aren't all the same
+    /// length.
+    pub fn from_rows<R: IntoIterator<Item = T>>(rows: impl IntoIterator<Item = R>) -> Option<Self> {
+        let mut cells = Vec::new();
+        let mut width = None;
+        let mut height = 0;
+        for row in rows {
+            let start = cells.len();
+            cells.extend(row);
+            if *width.get_or_insert(cells.len() - start) != cells.len() - start {
+                return None;
+            }
+            height += 1;
+        }
+        Some(Grid {
+            cells,
+            width: width.unwrap_or(0),
+            height,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    fn cell_index(&self, x: usize, y: usize) -> Option<usize> {
+        (x < self.width && y < self.height).then(|| y * self.width + x)
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Option<&T> {
+        self.cells.get(self.cell_index(x, y)?)
+    }
+
+    pub fn get_mut(&mut self, x: usize, y: usize) -> Option<&mut T> {
+        let index = self.cell_index(x, y)?;
+        self.cells.get_mut(index)
+    }
+
+    pub fn at(&self, point: Point) -> Option<&T> {
+        self.get(point.x, point.y)
+    }
+
+    pub fn at_mut(&mut self, point: Point) -> Option<&mut T> {
+        self.get_mut(point.x, point.y)
+    }
+
+    pub fn contains(&self, point: Point) -> bool {
+        self.cell_index(point.x, point.y).is_some()
+    }
+
+    /// The next point in `direction`, if it and `point` are both in the grid.
+    pub fn step(&self, point: Point, direction: Direction) -> Option<Point> {
+        self.offset(point, direction.vector(), Topology::Bounded)
+    }
+
+    /// The points from `point` to the edge of the grid in `direction`, not
+    /// including `point` itself.
+    pub fn walk(&self, point: Point, direction: Direction) -> impl Iterator<Item = Point> + '_ {
+        std::iter::successors(self.step(point, direction), move |&point| {
+            self.step(point, direction)
+        })
+    }
+
+    /// Every point in the grid, row by row.
+    pub fn points(&self) -> impl Iterator<Item = Point> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| Point { x, y }))
+    }
+
+    pub fn row(&self, y: usize) -> Option<&[T]> {
+        (y < self.height).then(|| &self.cells[y * self.width..(y + 1) * self.width])
+    }
+
+    pub fn rows(&self) -> impl DoubleEndedIterator<Item = &[T]> + ExactSizeIterator {
+        (0..self.height).map(|y| &self.cells[y * self.width..(y + 1) * self.width])
+    }
+
+    /// The cells in column `x` from top to bottom, which is empty if `x` is
+    /// out of bounds.
+    pub fn column(&self, x: usize) -> impl DoubleEndedIterator<Item = &T> {
+        let cells = if x < self.width {
+            &self.cells[x..]
+        } else {
+            &[]
+        };
+        cells.iter().step_by(self.width.max(1))
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl DoubleEndedIterator<Item = &T>> {
+        (0..self.width).map(|x| self.column(x))
+    }
+
+    /// Every cell with its position, row by row.
+    pub fn cells(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.points().zip(&self.cells)
+    }
+
+    /// The point `vector` away from `point`, if both are in the grid.
+    fn offset(&self, point: Point, vector: Vector, topology: Topology) -> Option<Point> {
+        self.contains(point).then_some(())?;
+        let point = match topology {
+            Topology::Bounded => point.checked_add(vector)?,
+            Topology::Toroidal => {
+                let wrap = |a: usize, da: isize, len: usize| {
+                    (a as isize + da.rem_euclid(len as isize)) as usize % len
+                };
+                Point {
+                    x: wrap(point.x, vector.dx, self.width),
+                    y: wrap(point.y, vector.dy, self.height),
+                }
+            }
+        };
+        self.contains(point).then_some(point)
+    }
+
+    /// The neighbours of `point`, in the order of the neighbourhood's offsets,
+    /// or none if `point` is outside the grid.
+    ///
+    /// On a torus narrower or shorter than the neighbourhood the same cell can
+    /// come up more than once, and can be `point` itself.
+    pub fn neighbours<'a>(
+        &'a self,
+        point: Point,
+        neighbourhood: &'a Neighbourhood,
+        topology: Topology,
+    ) -> impl Iterator<Item = Point> + 'a {
+        neighbourhood
+            .offsets()
+            .iter()
+            .filter_map(move |&offset| self.offset(point, offset, topology))
+    }
+
+    /// The cells sharing an edge with `point`, clockwise from north.
+    pub fn neighbours_4(&self, point: Point) -> impl Iterator<Item = Point> + '_ {
+        self.neighbours(point, &Neighbourhood::VonNeumann, Topology::Bounded)
+    }
+
+    /// The cells sharing an edge or a corner with `point`, clockwise from
+    /// north.
+    pub fn neighbours_8(&self, point: Point) -> impl Iterator<Item = Point> + '_ {
+        self.neighbours(point, &Neighbourhood::Moore, Topology::Bounded)
+    }
+
+    /// A grid of the same shape with `f` applied to every cell.
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            cells: self.cells.iter().map(f).collect(),
+            width: self.width,
+            height: self.height,
+        }
+    }
+}
+
+/// Panics if `(x, y)` is outside the grid.
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (x, y): (usize, usize)) -> &T {
+        self.get(x, y).expect("position outside the grid")
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, (x, y): (usize, usize)) -> &mut T {
+        self.get_mut(x, y).expect("position outside the grid")
+    }
+}
+
+/// Panics if `point` is outside the grid.
+impl<T> Index<Point> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, point: Point) -> &T {
+        self.at(point).expect("position outside the grid")
+    }
+}
+
+impl<T> IndexMut<Point> for Grid<T> {
+    fn index_mut(&mut self, point: Point) -> &mut T {
+        self.at_mut(point).expect("position outside the grid")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indoc::indoc;
+    use itertools::Itertools;
+
+    fn positions(points: impl Iterator<Item = Point>) -> Vec<(usize, usize)> {
+        points.map(<(usize, usize)>::from).collect()
+    }
+
+    fn grid() -> Grid<u8> {
+        Grid::parse(indoc! {
+            "
+            abc
+            def
+            "
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn parse() {
+        let grid = grid();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid.get(0, 0), Some(&b'a'));
+        assert_eq!(grid.get(2, 1), Some(&b'f'));
+        assert_eq!(grid.get(3, 0), None);
+        assert_eq!(grid.get(0, 2), None);
+        assert_eq!(grid[(1, 1)], b'e');
+        assert_eq!(grid, Grid::new(3, b"abcdef".to_vec()).unwrap());
+    }
+
+    #[yare::parameterized(
+        empty = { "", Ok((0, 0)) },
+        no_final_newline = { "ab\ncd", Ok((2, 2)) },
+        blank_lines = { "\n\n", Err(ParseGridError::TrailingBlankLine { line: 2 }) },
+        trailing_blank_line = { "ab\ncd\n\n", Err(ParseGridError::TrailingBlankLine { line: 3 }) },
+        crlf = { "ab\r\ncd\r\n", Ok((2, 2)) },
+        crlf_trailing_blank_line = { "ab\r\n\r\n", Err(ParseGridError::TrailingBlankLine { line: 2 }) },
+        crlf_ragged = { "ab\r\nc\r\n", Err(ParseGridError::Ragged { line: 2, len: 1, expected: 2 }) },
+        ragged = { "ab\nc", Err(ParseGridError::Ragged { line: 2, len: 1, expected: 2 }) },
+        blank_line_between = { "ab\n\ncd", Err(ParseGridError::Ragged { line: 2, len: 0, expected: 2 }) },
+    )]
+    fn strict(input: &str, expected: Result<(usize, usize), ParseGridError>) {
+        let grid = Grid::parse_with(input, Policy::Strict);
+        assert_eq!(grid.map(|grid| (grid.width(), grid.height())), expected);
+    }
+
+    #[yare::parameterized(
+        lf = { "ab\ncd\n", Ok((2, 2)) },
+        crlf = { "ab\r\ncd\r\n", Err(ParseGridError::CarriageReturn { line: 1 }) },
+        crlf_last_line = { "ab\ncd\r\n", Err(ParseGridError::CarriageReturn { line: 2 }) },
+        ragged = { "ab\nc", Err(ParseGridError::Ragged { line: 2, len: 1, expected: 2 }) },
+        trailing_blank_line = { "ab\n\n", Err(ParseGridError::TrailingBlankLine { line: 2 }) },
+    )]
+    fn exact(input: &str, expected: Result<(usize, usize), ParseGridError>) {
+        let grid = Grid::parse_with(input, Policy::Exact);
+        assert_eq!(grid.map(|grid| (grid.width(), grid.height())), expected);
+    }
+
+    #[yare::parameterized(
+        empty = { "", "" },
+        blank_lines = { "\n\n", "" },
+        trailing_blank_lines = { "ab\ncd\n\r\n\n", "ab\ncd" },
+        crlf = { "ab\r\ncd\r\n", "ab\ncd" },
+        short_line = { "ab\nc", "ab\nc." },
+        long_line = { "a\r\nbcd", "a..\nbcd" },
+        blank_line_between = { "ab\n\ncd", "ab\n..\ncd" },
+    )]
+    fn pad(input: &str, expected: &str) {
+        let grid = Grid::parse_with(input, Policy::Pad(b'.'));
+        assert_eq!(grid, Ok(Grid::parse(expected).unwrap()));
+    }
+
+    #[test]
+    fn diagnostics() {
+        let error = Grid::parse_with("...\n..\n", Policy::Strict).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 2 is 2 bytes long, but the first line is 3"
+        );
+        let error = Grid::parse_with("..\r\n", Policy::Exact).unwrap_err();
+        assert_eq!(error.to_string(), "line 1 ends with a carriage return");
+    }
+
+    #[yare::parameterized(
+        fits = { 2, 4, true },
+        partial_row = { 3, 4, false },
+        no_columns = { 0, 1, false },
+        nothing = { 0, 0, true },
+    )]
+    fn new(width: usize, cells: usize, valid: bool) {
+        assert_eq!(Grid::new(width, vec![0; cells]).is_some(), valid);
+    }
+
+    #[test]
+    fn rows_and_columns() {
+        let grid = grid();
+        assert_eq!(grid.rows().collect_vec(), [b"abc", b"def"]);
+        assert_eq!(grid.row(1), Some(&b"def"[..]));
+        assert_eq!(grid.row(2), None);
+        assert_eq!(
+            grid.columns()
+                .map(|column| column.copied().collect_vec())
+                .collect_vec(),
+            [b"ad", b"be", b"cf"]
+        );
+        assert_eq!(grid.column(3).count(), 0);
+        assert_eq!(
+            positions(grid.cells().map(|(point, _)| point)),
+            [(0, 0), (1, 0), (2, 0), (0, 1), (1, 1), (2, 1)]
+        );
+    }
+
+    #[yare::parameterized(
+        corner = { 0, 0, &[(1, 0), (0, 1)], &[(1, 0), (1, 1), (0, 1)] },
+        edge = { 1, 0, &[(2, 0), (1, 1), (0, 0)], &[(2, 0), (2, 1), (1, 1), (0, 1), (0, 0)] },
+        far_corner = { 2, 1, &[(2, 0), (1, 1)], &[(2, 0), (1, 1), (1, 0)] },
+        outside = { 3, 0, &[], &[] },
+    )]
+    fn neighbours(x: usize, y: usize, four: &[(usize, usize)], eight: &[(usize, usize)]) {
+        let grid = grid();
+        let point = Point::new(x, y);
+        assert_eq!(positions(grid.neighbours_4(point)), four);
+        assert_eq!(positions(grid.neighbours_8(point)), eight);
+    }
+
+    #[yare::parameterized(
+        corner = { 0, 0, &[(0, 1), (1, 0), (0, 1), (2, 0)] },
+        middle = { 1, 1, &[(1, 0), (2, 1), (1, 0), (0, 1)] },
+        outside = { 3, 0, &[] },
+    )]
+    fn toroidal(x: usize, y: usize, expected: &[(usize, usize)]) {
+        let grid = grid();
+        let neighbours = grid.neighbours(
+            Point::new(x, y),
+            &Neighbourhood::VonNeumann,
+            Topology::Toroidal,
+        );
+        assert_eq!(positions(neighbours), expected);
+    }
+
+    #[yare::parameterized(
+        bounded = { Topology::Bounded, &[(2, 1)] },
+        toroidal = { Topology::Toroidal, &[(2, 1), (1, 1), (2, 1), (1, 1)] },
+    )]
+    fn custom(topology: Topology, expected: &[(usize, usize)]) {
+        let knight = Neighbourhood::Custom(
+            [(2, 1), (-2, 1), (2, -1), (-2, -1)]
+                .map(Vector::from)
+                .to_vec(),
+        );
+        let grid = grid();
+        assert_eq!(
+            positions(grid.neighbours(Point::new(0, 0), &knight, topology)),
+            expected
+        );
+    }
+
+    #[yare::parameterized(
+        east = { (0, 0), Direction::E, &[(1, 0), (2, 0)] },
+        south_west = { (2, 0), Direction::SW, &[(1, 1)] },
+        off_the_edge = { (0, 1), Direction::S, &[] },
+        from_outside = { (5, 0), Direction::W, &[] },
+    )]
+    fn walk(from: (usize, usize), direction: Direction, expected: &[(usize, usize)]) {
+        let grid = grid();
+        assert_eq!(positions(grid.walk(from.into(), direction)), expected);
+    }
+
+    #[test]
+    fn points() {
+        let mut grid = grid();
+        let point = Point::new(2, 1);
+        assert!(grid.contains(point));
+        assert!(!grid.contains(Point::new(3, 1)));
+        assert_eq!(grid[point], b'f');
+        assert_eq!(grid.step(point, Direction::NW), Some(Point::new(1, 0)));
+        assert_eq!(grid.step(point, Direction::E), None);
+        grid[point] = b'z';
+        assert_eq!(grid.at(point), Some(&b'z'));
+        assert_eq!(grid.points().count(), 6);
+    }
+
+    #[test]
+    fn map() {
+        let upper = grid().map(u8::to_ascii_uppercase);
+        assert_eq!(upper, Grid::parse("ABC\nDEF").unwrap());
+    }
+}
@@ -0,0 +1,320 @@
+//! Positions in a grid, the steps between them and the eight compass
+//! directions, with `y` growing downwards.
+
+use std::{
+    fmt,
+    ops::{Add, Mul, Neg, Sub},
+};
+
+/// A position in a grid, with `(0, 0)` at the top left.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Point {
+    pub x: usize,
+    pub y: usize,
+}
+
+/// The step from one point to another.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Vector {
+    pub dx: isize,
+    pub dy: isize,
+}
+
+/// A compass direction, with north towards the top of the grid. Each one is
+/// numbered by its position in [`Direction::ALL`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    N = 0,
+    NE = 1,
+    E = 2,
+    SE = 3,
+    S = 4,
+    SW = 5,
+    W = 6,
+    NW = 7,
+}
+
+impl Point {
+    pub const fn new(x: usize, y: usize) -> Self {
+        Point { x, y }
+    }
+
+    /// The point `vector` away, or `None` if it would be left of or above
+    /// `(0, 0)`.
+    pub fn checked_add(self, vector: Vector) -> Option<Point> {
+        Some(Point {
+            x: self.x.checked_add_signed(vector.dx)?,
+            y: self.y.checked_add_signed(vector.dy)?,
+        })
+    }
+
+    /// The next point in `direction`, or `None` if it would be left of or
+    /// above `(0, 0)`. [`super::Grid::step`] also checks the far edges.
+    pub fn step(self, direction: Direction) -> Option<Point> {
+        self.checked_add(direction.vector())
+    }
+
+    /// The number of orthogonal steps to `other`.
+    pub fn manhattan(self, other: Point) -> usize {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
+    }
+
+    /// The number of steps to `other` when diagonal steps are allowed, so
+    /// the eight points around this one are all `1` away.
+    pub fn chebyshev(self, other: Point) -> usize {
+        self.x.abs_diff(other.x).max(self.y.abs_diff(other.y))
+    }
+}
+
+impl From<(usize, usize)> for Point {
+    fn from((x, y): (usize, usize)) -> Self {
+        Point { x, y }
+    }
+}
+
+impl From<Point> for (usize, usize) {
+    fn from(point: Point) -> Self {
+        (point.x, point.y)
+    }
+}
+
+impl fmt::Display for Point {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+/// Panics if either coordinate of the difference doesn't fit in an `isize`.
+impl Sub for Point {
+    type Output = Vector;
+
+    fn sub(self, other: Point) -> Vector {
+        let difference = |a: usize, b: usize| {
+            isize::try_from(a as i128 - b as i128).expect("points too far apart")
+        };
+        Vector {
+            dx: difference(self.x, other.x),
+            dy: difference(self.y, other.y),
+        }
+    }
+}
+
+impl Vector {
+    pub const fn new(dx: isize, dy: isize) -> Self {
+        Vector { dx, dy }
+    }
+
+    /// The vector turned a quarter turn clockwise, so north becomes east.
+    pub fn rotate_clockwise(self) -> Vector {
+        Vector {
+            dx: -self.dy,
+            dy: self.dx,
+        }
+    }
+
+    /// The vector turned a quarter turn anticlockwise, so north becomes west.
+    pub fn rotate_anticlockwise(self) -> Vector {
+        Vector {
+            dx: self.dy,
+            dy: -self.dx,
+        }
+    }
+
+    /// The length in orthogonal steps.
+    pub fn manhattan(self) -> usize {
+        self.dx.unsigned_abs() + self.dy.unsigned_abs()
+    }
+
+    /// The length in steps when diagonal steps are allowed.
+    pub fn chebyshev(self) -> usize {
+        self.dx.unsigned_abs().max(self.dy.unsigned_abs())
+    }
+}
+
+impl From<(isize, isize)> for Vector {
+    fn from((dx, dy): (isize, isize)) -> Self {
+        Vector { dx, dy }
+    }
+}
+
+impl From<Direction> for Vector {
+    fn from(direction: Direction) -> Self {
+        direction.vector()
+    }
+}
+
+impl Add for Vector {
+    type Output = Vector;
+
+    fn add(self, other: Vector) -> Vector {
+        Vector {
+            dx: self.dx + other.dx,
+            dy: self.dy + other.dy,
+        }
+    }
+}
+
+impl Sub for Vector {
+    type Output = Vector;
+
+    fn sub(self, other: Vector) -> Vector {
+        self + -other
+    }
+}
+
+impl Neg for Vector {
+    type Output = Vector;
+
+    fn neg(self) -> Vector {
+        Vector {
+            dx: -self.dx,
+            dy: -self.dy,
+        }
+    }
+}
+
+impl Mul<isize> for Vector {
+    type Output = Vector;
+
+    fn mul(self, factor: isize) -> Vector {
+        Vector {
+            dx: self.dx * factor,
+            dy: self.dy * factor,
+        }
+    }
+}
+
+impl Direction {
+    /// Every direction, clockwise from north.
+    pub const ALL: [Direction; 8] = [
+        Direction::N,
+        Direction::NE,
+        Direction::E,
+        Direction::SE,
+        Direction::S,
+        Direction::SW,
+        Direction::W,
+        Direction::NW,
+    ];
+
+    /// The directions sharing an edge, clockwise from north.
+    pub const ORTHOGONAL: [Direction; 4] = [Direction::N, Direction::E, Direction::S, Direction::W];
+
+    /// The directions sharing only a corner, clockwise from north east.
+    pub const DIAGONAL: [Direction; 4] =
+        [Direction::NE, Direction::SE, Direction::SW, Direction::NW];
+
+    /// The single step in this direction.
+    pub const fn vector(self) -> Vector {
+        let (dx, dy) = match self {
+            Direction::N => (0, -1),
+            Direction::NE => (1, -1),
+            Direction::E => (1, 0),
+            Direction::SE => (1, 1),
+            Direction::S => (0, 1),
+            Direction::SW => (-1, 1),
+            Direction::W => (-1, 0),
+            Direction::NW => (-1, -1),
+        };
+        Vector::new(dx, dy)
+    }
+
+    pub fn is_diagonal(self) -> bool {
+        Direction::DIAGONAL.contains(&self)
+    }
+
+    /// The direction `eighths` eighths of a turn clockwise, or anticlockwise
+    /// if negative.
+    pub fn rotate(self, eighths: isize) -> Direction {
+        Direction::ALL[(self as isize + eighths).rem_euclid(8) as usize]
+    }
+
+    /// A quarter turn clockwise.
+    pub fn turn_right(self) -> Direction {
+        self.rotate(2)
+    }
+
+    /// A quarter turn anticlockwise.
+    pub fn turn_left(self) -> Direction {
+        self.rotate(-2)
+    }
+
+    pub fn opposite(self) -> Direction {
+        self.rotate(4)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[yare::parameterized(
+        same = { (3, 4), (3, 4), 0, 0 },
+        orthogonal = { (3, 4), (3, 1), 3, 3 },
+        diagonal = { (0, 0), (2, 2), 4, 2 },
+        knight = { (5, 1), (4, 3), 3, 2 },
+    )]
+    fn distances(a: (usize, usize), b: (usize, usize), manhattan: usize, chebyshev: usize) {
+        let (a, b) = (Point::from(a), Point::from(b));
+        assert_eq!((a.manhattan(b), a.chebyshev(b)), (manhattan, chebyshev));
+        assert_eq!((b.manhattan(a), b.chebyshev(a)), (manhattan, chebyshev));
+        assert_eq!(
+            ((b - a).manhattan(), (b - a).chebyshev()),
+            (manhattan, chebyshev)
+        );
+    }
+
+    #[yare::parameterized(
+        inside = { (1, 1), Direction::NW, Some((0, 0)) },
+        left_edge = { (0, 1), Direction::SW, None },
+        top_edge = { (4, 0), Direction::NE, None },
+        unbounded_below = { (4, 0), Direction::SE, Some((5, 1)) },
+    )]
+    fn step(from: (usize, usize), direction: Direction, expected: Option<(usize, usize)>) {
+        assert_eq!(Point::from(from).step(direction), expected.map(Point::from));
+    }
+
+    #[test]
+    fn arithmetic() {
+        let a = Vector::new(2, -1);
+        let b = Vector::new(-3, 4);
+        assert_eq!(a + b, Vector::new(-1, 3));
+        assert_eq!(a - b, Vector::new(5, -5));
+        assert_eq!(-a, Vector::new(-2, 1));
+        assert_eq!(a * 3, Vector::new(6, -3));
+        assert_eq!(Point::new(1, 5) - Point::new(4, 2), Vector::new(-3, 3));
+        assert_eq!(Point::new(1, 5).checked_add(a * 3), Some(Point::new(7, 2)));
+        assert_eq!(Point::new(1, 5).checked_add(b), None);
+        assert_eq!(Point::new(1, 5).to_string(), "(1, 5)");
+    }
+
+    #[test]
+    fn rotation() {
+        assert_eq!(Direction::N.turn_right(), Direction::E);
+        assert_eq!(Direction::N.turn_left(), Direction::W);
+        assert_eq!(Direction::NE.opposite(), Direction::SW);
+        assert_eq!(Direction::W.rotate(1), Direction::NW);
+        assert_eq!(Direction::N.rotate(-1), Direction::NW);
+        assert_eq!(Direction::SE.rotate(17), Direction::S);
+        assert!(Direction::SW.is_diagonal());
+        assert!(!Direction::S.is_diagonal());
+        for (index, direction) in Direction::ALL.into_iter().enumerate() {
+            assert_eq!(direction as usize, index);
+        }
+        assert_eq!(
+            Direction::ALL.map(|direction| direction.vector().rotate_clockwise()),
+            Direction::ALL.map(|direction| direction.turn_right().vector())
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn quarter_turns(dx in -1000isize..1000, dy in -1000isize..1000) {
+            let vector = Vector::new(dx, dy);
+            prop_assert_eq!(vector.rotate_clockwise().rotate_anticlockwise(), vector);
+            prop_assert_eq!(vector.rotate_clockwise().rotate_clockwise(), -vector);
+            prop_assert_eq!(vector.rotate_clockwise().manhattan(), vector.manhattan());
+        }
+    }
+}
@@ -4,5 +4,8 @@ pub mod day_01;
 pub mod day_02;
 pub mod day_03;
 pub mod day_04;
+pub mod grid;
+pub mod matcher;
+pub mod rng;
 
 aoc_lib! { year = 2023 }
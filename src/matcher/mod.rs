@@ -0,0 +1,288 @@
+//! Multi-pattern byte string matching with an Aho-Corasick automaton.
+
+use std::collections::VecDeque;
+
+const ROOT: usize = 0;
+
+/// An occurrence of one of the patterns in a haystack.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Match<V> {
+    pub start: usize,
+    pub end: usize,
+    /// The index of the pattern that matched, in the order given to the matcher.
+    pub pattern: usize,
+    pub value: V,
+}
+
+/// A dense DFA over bytes, so each byte costs a single table lookup no matter
+/// how many patterns there are.
+#[derive(Debug, Clone)]
+struct Automaton {
+    transitions: Vec<[usize; 256]>,
+    /// The patterns ending at each state, longest first.
+    outputs: Vec<Vec<usize>>,
+}
+
+impl Automaton {
+    fn new<P: IntoIterator<Item = u8>>(patterns: impl Iterator<Item = P>, fold_case: bool) -> Self {
+        let mut children: Vec<[Option<usize>; 256]> = vec![[None; 256]];
+        let mut outputs = vec![Vec::new()];
+        for (index, pattern) in patterns.enumerate() {
+            let mut state = ROOT;
+            for byte in pattern.into_iter().map(|byte| {
+                if fold_case {
+                    byte.to_ascii_lowercase()
+                } else {
+                    byte
+                }
+            }) {
+                state = match children[state][byte as usize] {
+                    Some(next) => next,
+                    None => {
+                        children.push([None; 256]);
+                        outputs.push(Vec::new());
+                        children[state][byte as usize] = Some(children.len() - 1);
+                        children.len() - 1
+                    }
+                };
+            }
+            outputs[state].push(index);
+        }
+
+        // breadth first, so a state's failure link is always complete before
+        // its children need it
+        let mut transitions = vec![[ROOT; 256]; children.len()];
+        let mut failures = vec![ROOT; children.len()];
+        let mut queue = VecDeque::from([ROOT]);
+        while let Some(state) = queue.pop_front() {
+            for byte in 0..256 {
+                let fallback = match state {
+                    ROOT => ROOT,
+                    _ => transitions[failures[state]][byte],
+                };
+                match children[state][byte] {
+                    Some(child) => {
+                        failures[child] = fallback;
+                        let inherited = outputs[fallback].clone();
+                        outputs[child].extend(inherited);
+                        transitions[state][byte] = child;
+                        queue.push_back(child);
+                    }
+                    None => transitions[state][byte] = fallback,
+                }
+            }
+        }
+
+        if fold_case {
+            for row in &mut transitions {
+                for upper in b'A'..=b'Z' {
+                    row[upper as usize] = row[upper.to_ascii_lowercase() as usize];
+                }
+            }
+        }
+
+        Automaton {
+            transitions,
+            outputs,
+        }
+    }
+
+    /// The states reached after each byte, paired with the byte's index.
+    fn states<'a>(
+        &'a self,
+        bytes: impl Iterator<Item = (usize, &'a u8)> + 'a,
+    ) -> impl Iterator<Item = (usize, usize)> + 'a {
+        bytes.scan(ROOT, move |state, (index, &byte)| {
+            *state = self.transitions[*state][byte as usize];
+            Some((index, *state))
+        })
+    }
+}
+
+/// Finds every occurrence of a fixed set of patterns in a single pass.
+///
+/// A second automaton over the reversed patterns lets [`Matcher::find_last`]
+/// scan from the end of the haystack and stop at the first match it sees.
+#[derive(Debug, Clone)]
+pub struct Matcher<V> {
+    forward: Automaton,
+    reverse: Automaton,
+    patterns: Vec<(Vec<u8>, V)>,
+    max_len: usize,
+}
+
+impl<V: Copy> Matcher<V> {
+    pub fn new<P: AsRef<[u8]>>(patterns: impl IntoIterator<Item = (P, V)>) -> Self {
+        Self::build(patterns, false)
+    }
+
+    /// A matcher that treats ASCII letters of either case as the same.
+    pub fn new_ascii_case_insensitive<P: AsRef<[u8]>>(
+        patterns: impl IntoIterator<Item = (P, V)>,
+    ) -> Self {
+        Self::build(patterns, true)
+    }
+
+    fn build<P: AsRef<[u8]>>(patterns: impl IntoIterator<Item = (P, V)>, fold_case: bool) -> Self {
+        let patterns: Vec<(Vec<u8>, V)> = patterns
+            .into_iter()
+            .map(|(pattern, value)| (pattern.as_ref().to_vec(), value))
+            .collect();
+        assert!(
+            patterns.iter().all(|(pattern, _)| !pattern.is_empty()),
+            "patterns must not be empty"
+        );
+
+        let forward = Automaton::new(
+            patterns.iter().map(|(pattern, _)| pattern.iter().copied()),
+            fold_case,
+        );
+        let reverse = Automaton::new(
+            patterns
+                .iter()
+                .map(|(pattern, _)| pattern.iter().rev().copied()),
+            fold_case,
+        );
+        let max_len = patterns.iter().map(|(pattern, _)| pattern.len()).max();
+        Matcher {
+            forward,
+            reverse,
+            patterns,
+            max_len: max_len.unwrap_or(0),
+        }
+    }
+
+    fn match_at(&self, pattern: usize, start: usize) -> Match<V> {
+        let (bytes, value) = &self.patterns[pattern];
+        Match {
+            start,
+            end: start + bytes.len(),
+            pattern,
+            value: *value,
+        }
+    }
+
+    fn match_ending_at(&self, pattern: usize, end: usize) -> Match<V> {
+        self.match_at(pattern, end - self.patterns[pattern].0.len())
+    }
+
+    /// Every match, overlapping ones included, in order of where they end.
+    pub fn find_iter<'a>(&'a self, haystack: &'a [u8]) -> impl Iterator<Item = Match<V>> + 'a {
+        self.forward
+            .states(haystack.iter().enumerate())
+            .flat_map(move |(index, state)| {
+                self.forward.outputs[state]
+                    .iter()
+                    .map(move |&pattern| self.match_ending_at(pattern, index + 1))
+            })
+    }
+
+    /// The match that starts first, preferring earlier patterns on a tie.
+    pub fn find_first(&self, haystack: &[u8]) -> Option<Match<V>> {
+        let mut best: Option<Match<V>> = None;
+        for (index, state) in self.forward.states(haystack.iter().enumerate()) {
+            // nothing ending from here on can start early enough to win
+            if best.is_some_and(|best| index >= best.start + self.max_len) {
+                break;
+            }
+            for &pattern in &self.forward.outputs[state] {
+                let found = self.match_ending_at(pattern, index + 1);
+                if best.is_none_or(|best| (found.start, found.pattern) < (best.start, best.pattern))
+                {
+                    best = Some(found);
+                }
+            }
+        }
+        best
+    }
+
+    /// The match that starts last, preferring earlier patterns on a tie.
+    ///
+    /// Scanning backwards, the first reversed pattern to complete is the one
+    /// starting furthest along, so this only reads the bytes it has to.
+    pub fn find_last(&self, haystack: &[u8]) -> Option<Match<V>> {
+        self.reverse
+            .states(haystack.iter().enumerate().rev())
+            .find_map(|(index, state)| {
+                let pattern = *self.reverse.outputs[state].iter().min()?;
+                Some(self.match_at(pattern, index))
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    fn matcher() -> Matcher<u32> {
+        Matcher::new([("he", 0), ("she", 1), ("his", 2), ("hers", 3)])
+    }
+
+    #[test]
+    fn overlapping_matches() {
+        let found: Vec<(usize, usize, u32)> = matcher()
+            .find_iter(b"ushers")
+            .map(|m| (m.start, m.end, m.value))
+            .collect();
+        assert_eq!(found, [(1, 4, 1), (2, 4, 0), (2, 6, 3)]);
+    }
+
+    #[yare::parameterized(
+        none = { "xyz", None, None },
+        single = { "his", Some(2), Some(2) },
+        nested = { "ushers", Some(1), Some(0) },
+        apart = { "he likes his", Some(0), Some(2) },
+    )]
+    fn first_and_last(haystack: &str, first: Option<u32>, last: Option<u32>) {
+        let matcher = matcher();
+        assert_eq!(
+            matcher.find_first(haystack.as_bytes()).map(|m| m.value),
+            first
+        );
+        assert_eq!(
+            matcher.find_last(haystack.as_bytes()).map(|m| m.value),
+            last
+        );
+    }
+
+    #[yare::parameterized(
+        lower = { "ushers", true },
+        upper = { "USHERS", true },
+        mixed = { "uShErS", true },
+        other = { "usxers", false },
+    )]
+    fn case_insensitive(haystack: &str, matches_hers: bool) {
+        let matcher = Matcher::new_ascii_case_insensitive([("HErs", 0)]);
+        assert_eq!(
+            matcher.find_first(haystack.as_bytes()).is_some(),
+            matches_hers
+        );
+        assert_eq!(
+            matcher.find_first(b"hers").map(|m| (m.start, m.end)),
+            Some((0, 4))
+        );
+    }
+
+    #[test]
+    fn ties_prefer_earlier_patterns() {
+        let matcher = Matcher::new([("ab", 0), ("a", 1), ("abc", 2)]);
+        assert_eq!(matcher.find_first(b"abc").map(|m| m.value), Some(0));
+        assert_eq!(matcher.find_last(b"abc").map(|m| m.value), Some(0));
+    }
+
+    proptest! {
+        #[test]
+        fn first_and_last_match_every_match(
+            patterns in prop::collection::vec("[ab]{1,4}", 1..6),
+            haystack in "[abc]{0,30}",
+        ) {
+            let matcher = Matcher::new(patterns.iter().zip(0..));
+            let all: Vec<Match<u32>> = matcher.find_iter(haystack.as_bytes()).collect();
+            let first = all.iter().min_by_key(|m| (m.start, m.pattern)).copied();
+            let last = all.iter().max_by_key(|m| (m.start, std::cmp::Reverse(m.pattern))).copied();
+            prop_assert_eq!(matcher.find_first(haystack.as_bytes()), first);
+            prop_assert_eq!(matcher.find_last(haystack.as_bytes()), last);
+        }
+    }
+}